@@ -10,3 +10,5 @@ pub const kAXShowDefaultUIAction: &str = "AXShowDefaultUI";
 pub const kAXRaiseAction: &str = "AXRaise";
 pub const kAXShowMenuAction: &str = "AXShowMenu";
 pub const kAXPickAction: &str = "AXPick";
+// Declared by AppKit (NSAccessibilityScrollToVisibleAction) rather than HIServices.
+pub const kAXScrollToVisibleAction: &str = "AXScrollToVisible";
@@ -42,6 +42,8 @@ pub const kAXZoomButtonAttribute: &str = "AXZoomButton";
 pub const kAXMinimizeButtonAttribute: &str = "AXMinimizeButton";
 pub const kAXToolbarButtonAttribute: &str = "AXToolbarButton";
 pub const kAXFullScreenButtonAttribute: &str = "AXFullScreenButton";
+// Not declared in the SDK headers, but AppKit windows support it.
+pub const kAXFullScreenAttribute: &str = "AXFullScreen";
 pub const kAXProxyAttribute: &str = "AXProxy";
 pub const kAXGrowAreaAttribute: &str = "AXGrowArea";
 pub const kAXModalAttribute: &str = "AXModal";
@@ -61,6 +63,8 @@ pub const kAXMenuItemModifierControl: u32 = 1 << 2;
 pub const kAXMenuItemModifierNoCommand: u32 = 1 << 3;
 
 pub const kAXMenuBarAttribute: &str = "AXMenuBar";
+// Not declared in the SDK headers, but application elements report it.
+pub const kAXMenuBarVisibleAttribute: &str = "AXMenuBarVisible";
 pub const kAXWindowsAttribute: &str = "AXWindows";
 pub const kAXFrontmostAttribute: &str = "AXFrontmost";
 pub const kAXHiddenAttribute: &str = "AXHidden";
@@ -4,6 +4,7 @@ mod error;
 mod notification_constants;
 mod role_constants;
 mod text_attributed_string;
+mod text_marker;
 mod ui_element;
 mod value;
 mod value_constants;
@@ -14,6 +15,7 @@ pub use error::*;
 pub use notification_constants::*;
 pub use role_constants::*;
 pub use text_attributed_string::*;
+pub use text_marker::*;
 pub use ui_element::*;
 pub use value::*;
 pub use value_constants::*;
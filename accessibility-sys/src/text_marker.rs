@@ -0,0 +1,37 @@
+#![allow(non_upper_case_globals)]
+use core_foundation_sys::base::{CFAllocatorRef, CFIndex, CFTypeID};
+
+// Text markers aren't declared in the SDK headers. WebKit and other text engines that can't
+// express positions as a CFRange use them instead.
+pub const kAXStartTextMarkerAttribute: &str = "AXStartTextMarker";
+pub const kAXEndTextMarkerAttribute: &str = "AXEndTextMarker";
+pub const kAXSelectedTextMarkerRangeAttribute: &str = "AXSelectedTextMarkerRange";
+pub const kAXStringForTextMarkerRangeParameterizedAttribute: &str = "AXStringForTextMarkerRange";
+pub const kAXBoundsForTextMarkerRangeParameterizedAttribute: &str = "AXBoundsForTextMarkerRange";
+pub const kAXLengthForTextMarkerRangeParameterizedAttribute: &str = "AXLengthForTextMarkerRange";
+
+pub enum __AXTextMarker {}
+pub type AXTextMarkerRef = *mut __AXTextMarker;
+
+pub enum __AXTextMarkerRange {}
+pub type AXTextMarkerRangeRef = *mut __AXTextMarkerRange;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    pub fn AXTextMarkerGetTypeID() -> CFTypeID;
+    pub fn AXTextMarkerCreate(
+        allocator: CFAllocatorRef,
+        bytes: *const u8,
+        length: CFIndex,
+    ) -> AXTextMarkerRef;
+    pub fn AXTextMarkerGetLength(marker: AXTextMarkerRef) -> CFIndex;
+    pub fn AXTextMarkerGetBytePtr(marker: AXTextMarkerRef) -> *const u8;
+    pub fn AXTextMarkerRangeGetTypeID() -> CFTypeID;
+    pub fn AXTextMarkerRangeCreate(
+        allocator: CFAllocatorRef,
+        startMarker: AXTextMarkerRef,
+        endMarker: AXTextMarkerRef,
+    ) -> AXTextMarkerRangeRef;
+    pub fn AXTextMarkerRangeCopyStartMarker(range: AXTextMarkerRangeRef) -> AXTextMarkerRef;
+    pub fn AXTextMarkerRangeCopyEndMarker(range: AXTextMarkerRangeRef) -> AXTextMarkerRef;
+}
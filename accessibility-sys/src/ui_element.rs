@@ -1,6 +1,8 @@
 #![allow(non_upper_case_globals, non_camel_case_types)]
 use std::ffi::{c_uchar, c_void};
 
+#[cfg(feature = "private-api")]
+use core_foundation_sys::data::CFDataRef;
 use core_foundation_sys::{
     array::CFArrayRef,
     base::{CFIndex, CFTypeID, CFTypeRef},
@@ -109,6 +111,12 @@ extern "C" {
         virtualKey: CGKeyCode,
         keyDown: bool,
     ) -> AXError;
+    // Private SPI, not declared in the SDK headers. Linking these ties the binary to a macOS
+    // release that still exports them, so they're opt-in.
+    #[cfg(feature = "private-api")]
+    pub fn _AXUIElementCreateWithRemoteToken(token: CFDataRef) -> AXUIElementRef;
+    #[cfg(feature = "private-api")]
+    pub fn _AXUIElementRemoteTokenCreate(element: AXUIElementRef) -> CFDataRef;
     pub fn AXObserverGetTypeID() -> CFTypeID;
     pub fn AXObserverCreate(
         application: pid_t,
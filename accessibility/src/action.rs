@@ -1,6 +1,7 @@
 use accessibility_sys::{
-    kAXConfirmAction, kAXDecrementAction, kAXIncrementAction, kAXPickAction, kAXPressAction,
-    kAXRaiseAction, kAXShowAlternateUIAction, kAXShowDefaultUIAction, kAXShowMenuAction,
+    kAXCancelAction, kAXConfirmAction, kAXDecrementAction, kAXIncrementAction, kAXPickAction,
+    kAXPressAction, kAXRaiseAction, kAXScrollToVisibleAction, kAXShowAlternateUIAction,
+    kAXShowDefaultUIAction, kAXShowMenuAction,
 };
 use core_foundation::string::CFString;
 
@@ -38,9 +39,11 @@ define_actions![
     (increment, kAXIncrementAction),
     (decrement, kAXDecrementAction),
     (confirm, kAXConfirmAction),
+    (cancel, kAXCancelAction),
     (show_alternate_ui, kAXShowAlternateUIAction),
     (show_default_ui, kAXShowDefaultUIAction),
     (raise, kAXRaiseAction),
     (show_menu, kAXShowMenuAction),
     (pick, kAXPickAction),
+    (scroll_to_visible, kAXScrollToVisibleAction),
 ];
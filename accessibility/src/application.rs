@@ -0,0 +1,88 @@
+use accessibility_sys::pid_t;
+use cocoa::base::{id, nil};
+use core_foundation::{array::CFArray, string::CFString};
+use objc::{class, msg_send, rc::autoreleasepool, sel, sel_impl};
+
+use crate::{AXUIElement, AXUIElementAttributes, Error};
+
+/// Whichever application currently owns the menu bar.
+pub fn frontmost_application() -> Result<Application, Error> {
+    let pid = unsafe {
+        autoreleasepool(|| {
+            let workspace: id = msg_send![class![NSWorkspace], sharedWorkspace];
+            let app: id = msg_send![workspace, frontmostApplication];
+
+            if app != nil {
+                let pid: pid_t = msg_send![app, processIdentifier];
+                Some(pid)
+            } else {
+                None
+            }
+        })
+    };
+
+    pid.map(|pid| Application::from(AXUIElement::application(pid)))
+        .ok_or(Error::NotFound)
+}
+
+/// A running application, seen through its top-level accessibility element.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Application {
+    element: AXUIElement,
+}
+
+impl Application {
+    /// Returns `Error::NotFound` if no application is running with this pid.
+    pub fn from_pid(pid: pid_t) -> Result<Self, Error> {
+        AXUIElement::application_with_pid(pid).map(Self::from)
+    }
+
+    /// Finds a running instance of the application with this bundle identifier, e.g.
+    /// `com.apple.Safari`. Returns `Error::NotFound` if it isn't running.
+    pub fn from_bundle_id(bundle_id: &str) -> Result<Self, Error> {
+        AXUIElement::application_with_bundle(bundle_id).map(Self::from)
+    }
+
+    pub fn element(&self) -> &AXUIElement {
+        &self.element
+    }
+
+    pub fn into_element(self) -> AXUIElement {
+        self.element
+    }
+
+    pub fn pid(&self) -> Result<pid_t, Error> {
+        self.element.pid()
+    }
+
+    pub fn title(&self) -> Result<CFString, Error> {
+        self.element.title()
+    }
+
+    pub fn windows(&self) -> Result<CFArray<AXUIElement>, Error> {
+        self.element.windows()
+    }
+
+    pub fn focused_window(&self) -> Result<AXUIElement, Error> {
+        self.element.focused_window()
+    }
+
+    pub fn menu_bar(&self) -> Result<AXUIElement, Error> {
+        self.element.menu_bar()
+    }
+
+    /// Brings the application to the front without raising any particular window.
+    ///
+    /// This is deliberately not `AXUIElement::activate`, which needs a window to raise: an app
+    /// may have no windows at all, and when it does, coming to the front already restores its
+    /// own window order. To bring a specific window forward, call `activate` on that window.
+    pub fn activate(&self) -> Result<(), Error> {
+        self.element.set_frontmost(true)
+    }
+}
+
+impl From<AXUIElement> for Application {
+    fn from(element: AXUIElement) -> Self {
+        Self { element }
+    }
+}
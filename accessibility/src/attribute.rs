@@ -1,25 +1,54 @@
 use accessibility_sys::{
-    kAXAllowedValuesAttribute, kAXChildrenAttribute, kAXContentsAttribute, kAXDescriptionAttribute,
-    kAXElementBusyAttribute, kAXEnabledAttribute, kAXFocusedAttribute, kAXFocusedWindowAttribute,
-    kAXFrameAttribute, kAXFrontmostAttribute, kAXHelpAttribute, kAXIdentifierAttribute,
-    kAXLabelValueAttribute, kAXMainAttribute, kAXMainWindowAttribute, kAXMaxValueAttribute,
-    kAXMinValueAttribute, kAXMinimizedAttribute, kAXParentAttribute, kAXPlaceholderValueAttribute,
-    kAXPositionAttribute, kAXRoleAttribute, kAXRoleDescriptionAttribute,
-    kAXSelectedChildrenAttribute, kAXSizeAttribute, kAXSubroleAttribute, kAXTitleAttribute,
+    kAXAllowedValuesAttribute, kAXAttributedStringForRangeParameterizedAttribute,
+    kAXBoundsForRangeParameterizedAttribute, kAXBoundsForTextMarkerRangeParameterizedAttribute,
+    kAXCellForColumnAndRowParameterizedAttribute, kAXChildrenAttribute,
+    kAXColumnHeaderUIElementsAttribute, kAXColumnIndexRangeAttribute, kAXColumnsAttribute,
+    kAXContentsAttribute, kAXDescriptionAttribute, kAXDisclosedByRowAttribute,
+    kAXDisclosedRowsAttribute, kAXDisclosingAttribute, kAXDisclosureLevelAttribute,
+    kAXElementBusyAttribute, kAXEnabledAttribute, kAXEndTextMarkerAttribute,
+    kAXExtrasMenuBarAttribute, kAXFocusedApplicationAttribute, kAXFocusedAttribute,
+    kAXFocusedUIElementAttribute, kAXFocusedWindowAttribute, kAXFrameAttribute,
+    kAXFrontmostAttribute, kAXFullScreenAttribute, kAXHelpAttribute, kAXIdentifierAttribute,
+    kAXIndexAttribute, kAXInsertionPointLineNumberAttribute, kAXLabelValueAttribute,
+    kAXLayoutPointForScreenPointParameterizedAttribute,
+    kAXLayoutSizeForScreenSizeParameterizedAttribute,
+    kAXLengthForTextMarkerRangeParameterizedAttribute, kAXLineForIndexParameterizedAttribute,
+    kAXLinkedUIElementsAttribute, kAXMainAttribute, kAXMainWindowAttribute, kAXMaxValueAttribute,
+    kAXMenuBarAttribute, kAXMenuBarVisibleAttribute, kAXMinValueAttribute, kAXMinimizedAttribute,
+    kAXNumberOfCharactersAttribute, kAXParentAttribute, kAXPlaceholderValueAttribute,
+    kAXPositionAttribute, kAXRTFForRangeParameterizedAttribute,
+    kAXRangeForIndexParameterizedAttribute, kAXRangeForLineParameterizedAttribute,
+    kAXRangeForPositionParameterizedAttribute, kAXRoleAttribute, kAXRoleDescriptionAttribute,
+    kAXRowHeaderUIElementsAttribute, kAXRowIndexRangeAttribute, kAXRowsAttribute,
+    kAXScreenPointForLayoutPointParameterizedAttribute,
+    kAXScreenSizeForLayoutSizeParameterizedAttribute, kAXSelectedAttribute,
+    kAXSelectedChildrenAttribute, kAXSelectedRowsAttribute, kAXSelectedTextAttribute,
+    kAXSelectedTextMarkerRangeAttribute, kAXSelectedTextRangeAttribute,
+    kAXServesAsTitleForUIElementsAttribute, kAXSharedFocusElementsAttribute, kAXSizeAttribute,
+    kAXStartTextMarkerAttribute, kAXStringForRangeParameterizedAttribute,
+    kAXStringForTextMarkerRangeParameterizedAttribute, kAXStyleRangeForIndexParameterizedAttribute,
+    kAXSubroleAttribute, kAXTitleAttribute, kAXTitleUIElementAttribute,
     kAXTopLevelUIElementAttribute, kAXValueAttribute, kAXValueDescriptionAttribute,
-    kAXValueIncrementAttribute, kAXVisibleChildrenAttribute, kAXWindowAttribute,
-    kAXWindowsAttribute,
+    kAXValueIncrementAttribute, kAXVisibleCharacterRangeAttribute, kAXVisibleChildrenAttribute,
+    kAXWindowAttribute, kAXWindowsAttribute,
 };
 use core_foundation::{
     array::CFArray,
-    base::{CFType, TCFType},
+    attributed_string::CFAttributedString,
+    base::{CFRange, CFType, TCFType},
     boolean::CFBoolean,
+    data::CFData,
+    number::CFNumber,
     string::CFString,
 };
 use core_graphics_types::geometry::{CGPoint, CGRect, CGSize};
-use std::{fmt::Debug, marker::PhantomData};
+use std::{cell::RefCell, collections::HashSet, fmt::Debug, marker::PhantomData};
 
-use crate::{value::AXValue, AXUIElement, ElementFinder, Error};
+use crate::{
+    text_marker::{AXTextMarker, AXTextMarkerRange},
+    value::{AXValue, NumberKind},
+    AXUIElement, CachedElement, ElementFinder, Error,
+};
 
 pub trait TAXAttribute {
     type Value: TCFType;
@@ -46,6 +75,11 @@ impl<T> AXAttribute<T> {
 }
 
 macro_rules! constructor {
+    ($name:ident, Number<$typ:ty>, $const:ident $(,$setter:ident)?) => {
+        pub fn $name() -> AXAttribute<CFNumber> {
+            AXAttribute(CFString::from_static_string($const), PhantomData)
+        }
+    };
     ($name:ident, $typ:ty, $const:ident $(,$setter:ident)?) => {
         pub fn $name() -> AXAttribute<$typ> {
             AXAttribute(CFString::from_static_string($const), PhantomData)
@@ -53,7 +87,150 @@ macro_rules! constructor {
     };
 }
 
+#[derive(Clone)]
+pub struct AXParameterizedAttribute<P, T>(CFString, PhantomData<*const (P, T)>);
+
+impl<P, T> Debug for AXParameterizedAttribute<P, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<P, T> AXParameterizedAttribute<P, T> {
+    #[allow(non_snake_case)]
+    pub fn as_CFString(&self) -> &CFString {
+        &self.0
+    }
+}
+
+impl AXParameterizedAttribute<CFType, CFType> {
+    pub fn new(name: &CFString) -> Self {
+        AXParameterizedAttribute(name.to_owned(), PhantomData)
+    }
+}
+
+macro_rules! parameterized_constructor {
+    ($name:ident, $param:ty, $typ:ty, $const:ident) => {
+        pub fn $name() -> AXParameterizedAttribute<$param, $typ> {
+            AXParameterizedAttribute(CFString::from_static_string($const), PhantomData)
+        }
+    };
+}
+
+impl AXParameterizedAttribute<(), ()> {
+    parameterized_constructor!(
+        attributed_string_for_range,
+        AXValue<CFRange>,
+        CFAttributedString,
+        kAXAttributedStringForRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        bounds_for_range,
+        AXValue<CFRange>,
+        AXValue<CGRect>,
+        kAXBoundsForRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        bounds_for_text_marker_range,
+        AXTextMarkerRange,
+        AXValue<CGRect>,
+        kAXBoundsForTextMarkerRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        cell_for_column_and_row,
+        CFArray<CFNumber>,
+        AXUIElement,
+        kAXCellForColumnAndRowParameterizedAttribute
+    );
+    parameterized_constructor!(
+        layout_point_for_screen_point,
+        AXValue<CGPoint>,
+        AXValue<CGPoint>,
+        kAXLayoutPointForScreenPointParameterizedAttribute
+    );
+    parameterized_constructor!(
+        layout_size_for_screen_size,
+        AXValue<CGSize>,
+        AXValue<CGSize>,
+        kAXLayoutSizeForScreenSizeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        length_for_text_marker_range,
+        AXTextMarkerRange,
+        CFNumber,
+        kAXLengthForTextMarkerRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        line_for_index,
+        CFNumber,
+        CFNumber,
+        kAXLineForIndexParameterizedAttribute
+    );
+    parameterized_constructor!(
+        range_for_index,
+        CFNumber,
+        AXValue<CFRange>,
+        kAXRangeForIndexParameterizedAttribute
+    );
+    parameterized_constructor!(
+        range_for_line,
+        CFNumber,
+        AXValue<CFRange>,
+        kAXRangeForLineParameterizedAttribute
+    );
+    parameterized_constructor!(
+        range_for_position,
+        AXValue<CGPoint>,
+        AXValue<CFRange>,
+        kAXRangeForPositionParameterizedAttribute
+    );
+    parameterized_constructor!(
+        rtf_for_range,
+        AXValue<CFRange>,
+        CFData,
+        kAXRTFForRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        screen_point_for_layout_point,
+        AXValue<CGPoint>,
+        AXValue<CGPoint>,
+        kAXScreenPointForLayoutPointParameterizedAttribute
+    );
+    parameterized_constructor!(
+        screen_size_for_layout_size,
+        AXValue<CGSize>,
+        AXValue<CGSize>,
+        kAXScreenSizeForLayoutSizeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        string_for_range,
+        AXValue<CFRange>,
+        CFString,
+        kAXStringForRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        string_for_text_marker_range,
+        AXTextMarkerRange,
+        CFString,
+        kAXStringForTextMarkerRangeParameterizedAttribute
+    );
+    parameterized_constructor!(
+        style_range_for_index,
+        CFNumber,
+        AXValue<CFRange>,
+        kAXStyleRangeForIndexParameterizedAttribute
+    );
+}
+
 macro_rules! accessor {
+    (@decl $name:ident, Number<$typ:ty>, $const:ident, $setter:ident) => {
+        accessor!(@decl $name, Number<$typ>, $const);
+        fn $setter(&self, value: $typ) -> Result<(), Error>;
+    };
+    (@decl $name:ident, CFBoolean, $const:ident, $setter:ident) => {
+        accessor!(@decl $name, CFBoolean, $const);
+        fn $setter(&self, value: impl Into<CFBoolean>) -> Result<(), Error>;
+    };
     (@decl $name:ident, AXValue<$typ:ty>, $const:ident, $setter:ident) => {
         accessor!(@decl $name, AXValue<$typ>, $const);
         fn $setter(&self, value: impl Into<$typ>) -> Result<(), Error>;
@@ -62,16 +239,34 @@ macro_rules! accessor {
         accessor!(@decl $name, $typ, $const);
         fn $setter(&self, value: impl Into<$typ>) -> Result<(), Error>;
     };
+    (@decl $name:ident, Number<$typ:ty>, $const:ident) => {
+        fn $name(&self) -> Result<$typ, Error>;
+    };
+    (@decl $name:ident, CFBoolean, $const:ident) => {
+        fn $name(&self) -> Result<bool, Error>;
+    };
     (@decl $name:ident, AXValue<$typ:ty>, $const:ident) => {
         fn $name(&self) -> Result<$typ, Error>;
     };
     (@decl $name:ident, $typ:ty, $const:ident) => {
         fn $name(&self) -> Result<$typ, Error>;
     };
+    (@impl $name:ident, Number<$typ:ty>, $const:ident, $setter:ident) => {
+        accessor!(@impl $name, Number<$typ>, $const);
+        fn $setter(&self, value: $typ) -> Result<(), Error> {
+            self.set_attribute(&AXAttribute::$name(), CFNumber::from(value))
+        }
+    };
+    (@impl $name:ident, CFBoolean, $const:ident, $setter:ident) => {
+        accessor!(@impl $name, CFBoolean, $const);
+        fn $setter(&self, value: impl Into<CFBoolean>) -> Result<(), Error> {
+            self.set_attribute(&AXAttribute::$name(), value)
+        }
+    };
     (@impl $name:ident, AXValue<$typ:ty>, $const:ident, $setter:ident) => {
         accessor!(@impl $name, AXValue<$typ>, $const);
         fn $setter(&self, value: impl Into<$typ>) -> Result<(), Error> {
-            self.set_attribute(&AXAttribute::$name(), AXValue::new(&value.into()).expect("wrong type"))
+            self.set_attribute(&AXAttribute::$name(), AXValue::new(&value.into())?)
         }
     };
     (@impl $name:ident, $typ:ty, $const:ident, $setter:ident) => {
@@ -80,9 +275,21 @@ macro_rules! accessor {
             self.set_attribute(&AXAttribute::$name(), value)
         }
     };
+    (@impl $name:ident, Number<$typ:ty>, $const:ident) => {
+        fn $name(&self) -> Result<$typ, Error> {
+            let number = self.attribute(&AXAttribute::$name())?;
+            <$typ as NumberKind>::from_number(&number).map_err(|_| Error::UnconvertibleNumber)
+        }
+    };
+    (@impl $name:ident, CFBoolean, $const:ident) => {
+        fn $name(&self) -> Result<bool, Error> {
+            self.attribute(&AXAttribute::$name()).map(bool::from)
+        }
+    };
     (@impl $name:ident, AXValue<$typ:ty>, $const:ident) => {
         fn $name(&self) -> Result<$typ, Error> {
-            self.attribute(&AXAttribute::$name()).map(|v| v.value().expect("wrong type"))
+            let value = self.attribute(&AXAttribute::$name())?;
+            Ok(value.value()?)
         }
     };
     (@impl $name:ident, $typ:ty, $const:ident) => {
@@ -93,29 +300,30 @@ macro_rules! accessor {
 }
 
 macro_rules! debug_field {
-    ($self:ident, $fmt:ident, $name:ident, AXUIElement, $($rest:tt)*) => {
-        debug_field!(@short $self, $fmt, $name, AXUIElement, $long_name);
-    };
-    ($self:ident, $fmt:ident, $name:ident, CFArray<AXUIElement>, $($rest:tt)*) => {
-            let $name = $self.$name();
-            if let Ok(value) = &$name {
-                $fmt.field(stringify!($name), &NoAlternate(Forward(&value)));
-            }
-    };
-    ($self:ident, $fmt:ident, $name:ident, CFBoolean, $($rest:tt)*) => {
-            let $name = $self.$name();
-            if let Ok(value) = &$name {
-                let value: bool = value.clone().into();
-                $fmt.field(stringify!($name), &value);
+    ($self:ident, $fmt:ident, $depth:ident, $visited:ident, $name:ident, AXUIElement, $($rest:tt)*) => {
+        let $name = $self.$name();
+        if let Ok(value) = &$name {
+            if $depth > 0 {
+                $fmt.field(stringify!($name), &value.debug_nested($depth - 1, $visited));
+            } else {
+                $fmt.field(stringify!($name), &NoAlternate(value));
             }
+        }
     };
-    (@short $self:ident, $fmt:ident, $name:ident, $type:ty, $($rest:tt)*) => {
+    ($self:ident, $fmt:ident, $depth:ident, $visited:ident, $name:ident, CFArray<AXUIElement>, $($rest:tt)*) => {
         let $name = $self.$name();
         if let Ok(value) = &$name {
-            $fmt.field(stringify!($name), &NoAlternate(value));
+            if $depth > 0 {
+                $fmt.field(stringify!($name), &Forward(&value, $depth, $visited));
+            } else {
+                $fmt.field(
+                    stringify!($name),
+                    &NoAlternate(Forward(&value, $depth, $visited)),
+                );
+            }
         }
     };
-    ($self:ident, $fmt:ident, $name:ident, $type:ty, $($rest:tt)*) => {
+    ($self:ident, $fmt:ident, $depth:ident, $visited:ident, $name:ident, $type:ty, $($rest:tt)*) => {
         let $name = $self.$name();
         if let Ok(value) = &$name {
             $fmt.field(stringify!($name), &value);
@@ -130,13 +338,21 @@ impl<T: Debug> Debug for NoAlternate<T> {
     }
 }
 
-struct Forward<'a>(&'a CFArray<AXUIElement>);
+struct Forward<'a>(
+    &'a CFArray<AXUIElement>,
+    usize,
+    &'a RefCell<HashSet<AXUIElement>>,
+);
 impl<'a> Debug for Forward<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut list = f.debug_list();
         for elem in self.0 {
             let elem: &AXUIElement = &*elem;
-            list.entry(elem);
+            if self.1 > 0 {
+                list.entry(&elem.debug_nested(self.1 - 1, self.2));
+            } else {
+                list.entry(elem);
+            }
         }
         list.finish()
     }
@@ -164,18 +380,42 @@ macro_rules! define_attributes {
             $(accessor!(@impl $($args)*);)*
         }
 
+        impl AXUIElementAttributes for CachedElement {
+            $(accessor!(@impl $($args)*);)*
+        }
+
         impl AXUIElement {
-            pub(crate) fn debug_all(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            pub(crate) fn debug_all(
+                &self,
+                f: &mut std::fmt::Formatter<'_>,
+                depth: usize,
+                visited: &RefCell<HashSet<AXUIElement>>,
+            ) -> std::fmt::Result {
+                // Elements currently being printed further up; parent and child links can loop.
+                if !visited.borrow_mut().insert(self.clone()) {
+                    return write!(f, "<cycle>");
+                }
+                let result = self.debug_fields(f, depth, visited);
+                visited.borrow_mut().remove(self);
+                result
+            }
+
+            fn debug_fields(
+                &self,
+                f: &mut std::fmt::Formatter<'_>,
+                depth: usize,
+                visited: &RefCell<HashSet<AXUIElement>>,
+            ) -> std::fmt::Result {
                 let mut fmt = f.debug_struct("AXUIElement");
 
-                $(debug_field!(self, fmt, $($args)*);)*
+                $(debug_field!(self, fmt, depth, visited, $($args)*);)*
 
-                let Ok(attr_names) = self.attribute_names() else {
+                let Ok(attr_names) = self.attribute_names_iter() else {
                     return fmt.finish();
                 };
-                let attr_names: Vec<CFString> = attr_names.iter().filter(|name| {
-                    $(**name != define_attributes!(@get_sys_name $($args)*) &&)* true
-                }).map(|n| n.clone()).collect();
+                let attr_names = attr_names.filter(|name| {
+                    $(*name != define_attributes!(@get_sys_name $($args)*) &&)* true
+                });
                 for name in attr_names {
                     let attr = AXAttribute(name, PhantomData);
                     if let Ok(val) = self.attribute::<CFType>(&attr) {
@@ -201,22 +441,82 @@ define_attributes![
     // The rest are in alphabetical order.
     (allowed_values, CFArray<CFType>, kAXAllowedValuesAttribute),
     (children, CFArray<AXUIElement>, kAXChildrenAttribute),
+    (
+        column_header_ui_elements,
+        CFArray<AXUIElement>,
+        kAXColumnHeaderUIElementsAttribute
+    ),
+    (
+        column_index_range,
+        AXValue<CFRange>,
+        kAXColumnIndexRangeAttribute
+    ),
+    (columns, CFArray<AXUIElement>, kAXColumnsAttribute),
     (contents, AXUIElement, kAXContentsAttribute),
     (description, CFString, kAXDescriptionAttribute),
+    (disclosed_by_row, AXUIElement, kAXDisclosedByRowAttribute),
+    (
+        disclosed_rows,
+        CFArray<AXUIElement>,
+        kAXDisclosedRowsAttribute
+    ),
+    (
+        disclosing,
+        CFBoolean,
+        kAXDisclosingAttribute,
+        set_disclosing
+    ),
+    (disclosure_level, Number<i64>, kAXDisclosureLevelAttribute),
     (element_busy, CFBoolean, kAXElementBusyAttribute),
     (enabled, CFBoolean, kAXEnabledAttribute),
-    (focused, CFBoolean, kAXFocusedAttribute),
+    (end_text_marker, AXTextMarker, kAXEndTextMarkerAttribute),
+    (extras_menu_bar, AXUIElement, kAXExtrasMenuBarAttribute),
+    (focused, CFBoolean, kAXFocusedAttribute, set_focused),
+    (
+        focused_application,
+        AXUIElement,
+        kAXFocusedApplicationAttribute
+    ),
+    (
+        focused_ui_element,
+        AXUIElement,
+        kAXFocusedUIElementAttribute
+    ),
     (focused_window, AXUIElement, kAXFocusedWindowAttribute),
     (frontmost, CFBoolean, kAXFrontmostAttribute, set_frontmost),
     (frame, AXValue<CGRect>, kAXFrameAttribute),
+    (
+        fullscreen,
+        CFBoolean,
+        kAXFullScreenAttribute,
+        set_fullscreen
+    ),
     (help, CFString, kAXHelpAttribute),
     (identifier, CFString, kAXIdentifierAttribute),
+    (index, Number<i64>, kAXIndexAttribute),
+    (
+        insertion_point_line_number,
+        Number<i64>,
+        kAXInsertionPointLineNumberAttribute
+    ),
     (label_value, CFString, kAXLabelValueAttribute),
+    (
+        linked_ui_elements,
+        CFArray<AXUIElement>,
+        kAXLinkedUIElementsAttribute
+    ),
     (main, CFBoolean, kAXMainAttribute, set_main),
     (main_window, AXUIElement, kAXMainWindowAttribute),
     (max_value, CFType, kAXMaxValueAttribute),
+    (menu_bar, AXUIElement, kAXMenuBarAttribute),
+    (menu_bar_visible, CFBoolean, kAXMenuBarVisibleAttribute),
     (min_value, CFType, kAXMinValueAttribute),
-    (minimized, CFBoolean, kAXMinimizedAttribute),
+    (minimized, CFBoolean, kAXMinimizedAttribute, set_minimized),
+    (
+        number_of_characters,
+        Number<i64>,
+        kAXNumberOfCharactersAttribute
+    ),
     (parent, AXUIElement, kAXParentAttribute),
     (placeholder_value, CFString, kAXPlaceholderValueAttribute),
     (
@@ -226,13 +526,51 @@ define_attributes![
         set_position
     ),
     (role_description, CFString, kAXRoleDescriptionAttribute),
+    (
+        row_header_ui_elements,
+        CFArray<AXUIElement>,
+        kAXRowHeaderUIElementsAttribute
+    ),
+    (row_index_range, AXValue<CFRange>, kAXRowIndexRangeAttribute),
+    (rows, CFArray<AXUIElement>, kAXRowsAttribute),
+    (selected, CFBoolean, kAXSelectedAttribute, set_selected),
     (
         selected_children,
         CFArray<AXUIElement>,
-        kAXSelectedChildrenAttribute
+        kAXSelectedChildrenAttribute,
+        set_selected_children
+    ),
+    (
+        selected_rows,
+        CFArray<AXUIElement>,
+        kAXSelectedRowsAttribute
+    ),
+    (selected_text, CFString, kAXSelectedTextAttribute),
+    (
+        selected_text_marker_range,
+        AXTextMarkerRange,
+        kAXSelectedTextMarkerRangeAttribute
+    ),
+    (
+        selected_text_range,
+        AXValue<CFRange>,
+        kAXSelectedTextRangeAttribute,
+        set_selected_text_range
+    ),
+    (
+        serves_as_title_for_ui_elements,
+        CFArray<AXUIElement>,
+        kAXServesAsTitleForUIElementsAttribute
+    ),
+    (
+        shared_focus_elements,
+        CFArray<AXUIElement>,
+        kAXSharedFocusElementsAttribute
     ),
     (size, AXValue<CGSize>, kAXSizeAttribute, set_size),
+    (start_text_marker, AXTextMarker, kAXStartTextMarkerAttribute),
     (title, CFString, kAXTitleAttribute),
+    (title_ui_element, AXUIElement, kAXTitleUIElementAttribute),
     (
         top_level_ui_element,
         AXUIElement,
@@ -241,6 +579,12 @@ define_attributes![
     (value, CFType, kAXValueAttribute, set_value),
     (value_description, CFString, kAXValueDescriptionAttribute),
     (value_increment, CFType, kAXValueIncrementAttribute),
+    (
+        visible_character_range,
+        AXValue<CFRange>,
+        kAXVisibleCharacterRangeAttribute,
+        set_visible_character_range
+    ),
     (
         visible_children,
         CFArray<AXUIElement>,
@@ -249,3 +593,208 @@ define_attributes![
     (window, AXUIElement, kAXWindowAttribute),
     (windows, CFArray<AXUIElement>, kAXWindowsAttribute),
 ];
+
+macro_rules! define_attribute_names {
+    ($(($variant:ident, $const:ident)),* $(,)?) => {
+        /// The standard attribute names, for when the attribute to read is only known at runtime.
+        /// `Other` holds any name that isn't one of these.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum Attribute {
+            $($variant,)*
+            Other(CFString),
+        }
+
+        impl Attribute {
+            /// Every standard attribute, in header order.
+            pub const ALL: &'static [Attribute] = &[$(Attribute::$variant),*];
+
+            pub fn as_cfstring(&self) -> CFString {
+                match self {
+                    $(Self::$variant => CFString::from_static_string(accessibility_sys::$const),)*
+                    Self::Other(name) => name.clone(),
+                }
+            }
+        }
+
+        impl From<CFString> for Attribute {
+            fn from(name: CFString) -> Self {
+                match &*name.to_string() {
+                    $(accessibility_sys::$const => Self::$variant,)*
+                    _ => Self::Other(name),
+                }
+            }
+        }
+    };
+}
+
+define_attribute_names![
+    (Role, kAXRoleAttribute),
+    (Subrole, kAXSubroleAttribute),
+    (RoleDescription, kAXRoleDescriptionAttribute),
+    (Help, kAXHelpAttribute),
+    (Title, kAXTitleAttribute),
+    (Value, kAXValueAttribute),
+    (ValueDescription, kAXValueDescriptionAttribute),
+    (MinValue, kAXMinValueAttribute),
+    (MaxValue, kAXMaxValueAttribute),
+    (ValueIncrement, kAXValueIncrementAttribute),
+    (AllowedValues, kAXAllowedValuesAttribute),
+    (PlaceholderValue, kAXPlaceholderValueAttribute),
+    (Enabled, kAXEnabledAttribute),
+    (ElementBusy, kAXElementBusyAttribute),
+    (Focused, kAXFocusedAttribute),
+    (Parent, kAXParentAttribute),
+    (Children, kAXChildrenAttribute),
+    (SelectedChildren, kAXSelectedChildrenAttribute),
+    (VisibleChildren, kAXVisibleChildrenAttribute),
+    (Window, kAXWindowAttribute),
+    (TopLevelUIElement, kAXTopLevelUIElementAttribute),
+    (Frame, kAXFrameAttribute),
+    (Position, kAXPositionAttribute),
+    (Size, kAXSizeAttribute),
+    (Orientation, kAXOrientationAttribute),
+    (Description, kAXDescriptionAttribute),
+    (SelectedText, kAXSelectedTextAttribute),
+    (SelectedTextRange, kAXSelectedTextRangeAttribute),
+    (SelectedTextRanges, kAXSelectedTextRangesAttribute),
+    (VisibleCharacterRange, kAXVisibleCharacterRangeAttribute),
+    (NumberOfCharacters, kAXNumberOfCharactersAttribute),
+    (SharedTextUIElements, kAXSharedTextUIElementsAttribute),
+    (SharedCharacterRange, kAXSharedCharacterRangeAttribute),
+    (SharedFocusElements, kAXSharedFocusElementsAttribute),
+    (
+        InsertionPointLineNumber,
+        kAXInsertionPointLineNumberAttribute
+    ),
+    (Main, kAXMainAttribute),
+    (Minimized, kAXMinimizedAttribute),
+    (CloseButton, kAXCloseButtonAttribute),
+    (ZoomButton, kAXZoomButtonAttribute),
+    (MinimizeButton, kAXMinimizeButtonAttribute),
+    (ToolbarButton, kAXToolbarButtonAttribute),
+    (FullScreenButton, kAXFullScreenButtonAttribute),
+    (FullScreen, kAXFullScreenAttribute),
+    (Proxy, kAXProxyAttribute),
+    (GrowArea, kAXGrowAreaAttribute),
+    (Modal, kAXModalAttribute),
+    (DefaultButton, kAXDefaultButtonAttribute),
+    (CancelButton, kAXCancelButtonAttribute),
+    (MenuItemCmdChar, kAXMenuItemCmdCharAttribute),
+    (MenuItemCmdVirtualKey, kAXMenuItemCmdVirtualKeyAttribute),
+    (MenuItemCmdGlyph, kAXMenuItemCmdGlyphAttribute),
+    (MenuItemCmdModifiers, kAXMenuItemCmdModifiersAttribute),
+    (MenuItemMarkChar, kAXMenuItemMarkCharAttribute),
+    (
+        MenuItemPrimaryUIElement,
+        kAXMenuItemPrimaryUIElementAttribute
+    ),
+    (MenuBar, kAXMenuBarAttribute),
+    (Windows, kAXWindowsAttribute),
+    (Frontmost, kAXFrontmostAttribute),
+    (Hidden, kAXHiddenAttribute),
+    (MainWindow, kAXMainWindowAttribute),
+    (FocusedWindow, kAXFocusedWindowAttribute),
+    (FocusedUIElement, kAXFocusedUIElementAttribute),
+    (ExtrasMenuBar, kAXExtrasMenuBarAttribute),
+    (Header, kAXHeaderAttribute),
+    (Edited, kAXEditedAttribute),
+    (ValueWraps, kAXValueWrapsAttribute),
+    (Tabs, kAXTabsAttribute),
+    (TitleUIElement, kAXTitleUIElementAttribute),
+    (HorizontalScrollBar, kAXHorizontalScrollBarAttribute),
+    (VerticalScrollBar, kAXVerticalScrollBarAttribute),
+    (OverflowButton, kAXOverflowButtonAttribute),
+    (Filename, kAXFilenameAttribute),
+    (Expanded, kAXExpandedAttribute),
+    (Selected, kAXSelectedAttribute),
+    (Splitters, kAXSplittersAttribute),
+    (NextContents, kAXNextContentsAttribute),
+    (Document, kAXDocumentAttribute),
+    (DecrementButton, kAXDecrementButtonAttribute),
+    (IncrementButton, kAXIncrementButtonAttribute),
+    (PreviousContents, kAXPreviousContentsAttribute),
+    (Contents, kAXContentsAttribute),
+    (Incrementor, kAXIncrementorAttribute),
+    (HourField, kAXHourFieldAttribute),
+    (MinuteField, kAXMinuteFieldAttribute),
+    (SecondField, kAXSecondFieldAttribute),
+    (AMPMField, kAXAMPMFieldAttribute),
+    (DayField, kAXDayFieldAttribute),
+    (MonthField, kAXMonthFieldAttribute),
+    (YearField, kAXYearFieldAttribute),
+    (Url, kAXURLAttribute),
+    (LabelUIElements, kAXLabelUIElementsAttribute),
+    (LabelValue, kAXLabelValueAttribute),
+    (ShownMenuUIElement, kAXShownMenuUIElementAttribute),
+    (
+        ServesAsTitleForUIElements,
+        kAXServesAsTitleForUIElementsAttribute
+    ),
+    (LinkedUIElements, kAXLinkedUIElementsAttribute),
+    (Rows, kAXRowsAttribute),
+    (VisibleRows, kAXVisibleRowsAttribute),
+    (SelectedRows, kAXSelectedRowsAttribute),
+    (Columns, kAXColumnsAttribute),
+    (VisibleColumns, kAXVisibleColumnsAttribute),
+    (SelectedColumns, kAXSelectedColumnsAttribute),
+    (SortDirection, kAXSortDirectionAttribute),
+    (Index, kAXIndexAttribute),
+    (Disclosing, kAXDisclosingAttribute),
+    (DisclosedRows, kAXDisclosedRowsAttribute),
+    (DisclosedByRow, kAXDisclosedByRowAttribute),
+    (DisclosureLevel, kAXDisclosureLevelAttribute),
+    (MatteHole, kAXMatteHoleAttribute),
+    (MatteContentUIElement, kAXMatteContentUIElementAttribute),
+    (MarkerUIElements, kAXMarkerUIElementsAttribute),
+    (Units, kAXUnitsAttribute),
+    (UnitDescription, kAXUnitDescriptionAttribute),
+    (MarkerType, kAXMarkerTypeAttribute),
+    (MarkerTypeDescription, kAXMarkerTypeDescriptionAttribute),
+    (IsApplicationRunning, kAXIsApplicationRunningAttribute),
+    (SearchButton, kAXSearchButtonAttribute),
+    (ClearButton, kAXClearButtonAttribute),
+    (FocusedApplication, kAXFocusedApplicationAttribute),
+    (RowCount, kAXRowCountAttribute),
+    (ColumnCount, kAXColumnCountAttribute),
+    (OrderedByRow, kAXOrderedByRowAttribute),
+    (WarningValue, kAXWarningValueAttribute),
+    (CriticalValue, kAXCriticalValueAttribute),
+    (SelectedCells, kAXSelectedCellsAttribute),
+    (VisibleCells, kAXVisibleCellsAttribute),
+    (RowHeaderUIElements, kAXRowHeaderUIElementsAttribute),
+    (ColumnHeaderUIElements, kAXColumnHeaderUIElementsAttribute),
+    (RowIndexRange, kAXRowIndexRangeAttribute),
+    (ColumnIndexRange, kAXColumnIndexRangeAttribute),
+    (HorizontalUnits, kAXHorizontalUnitsAttribute),
+    (VerticalUnits, kAXVerticalUnitsAttribute),
+    (
+        HorizontalUnitDescription,
+        kAXHorizontalUnitDescriptionAttribute
+    ),
+    (VerticalUnitDescription, kAXVerticalUnitDescriptionAttribute),
+    (Handles, kAXHandlesAttribute),
+    (Text, kAXTextAttribute),
+    (VisibleText, kAXVisibleTextAttribute),
+    (IsEditable, kAXIsEditableAttribute),
+    (ColumnTitles, kAXColumnTitlesAttribute),
+    (Identifier, kAXIdentifierAttribute),
+    (AlternateUIVisible, kAXAlternateUIVisibleAttribute),
+];
+
+impl From<&str> for Attribute {
+    fn from(name: &str) -> Self {
+        CFString::new(name).into()
+    }
+}
+
+impl<T> From<&AXAttribute<T>> for Attribute {
+    fn from(attribute: &AXAttribute<T>) -> Self {
+        attribute.as_CFString().clone().into()
+    }
+}
+
+impl From<Attribute> for AXAttribute<CFType> {
+    fn from(attribute: Attribute) -> Self {
+        AXAttribute::new(&attribute.as_cfstring())
+    }
+}
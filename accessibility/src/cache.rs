@@ -0,0 +1,69 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use core_foundation::base::{CFType, TCFType};
+
+use crate::{util::cast_attribute, AXAttribute, AXErrorKind, AXUIElement, Error};
+
+/// Wraps an element and remembers every attribute read through it until `invalidate` is called.
+///
+/// Values are cached, and so are reads that found the attribute missing or unsupported. Other
+/// errors aren't, so the next read tries again.
+pub struct CachedElement {
+    element: AXUIElement,
+    cache: RefCell<HashMap<String, Result<CFType, AXErrorKind>>>,
+}
+
+impl CachedElement {
+    pub fn new(element: AXUIElement) -> Self {
+        Self {
+            element,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn element(&self) -> &AXUIElement {
+        &self.element
+    }
+
+    pub fn invalidate(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    pub fn attribute<T: TCFType>(&self, attribute: &AXAttribute<T>) -> Result<T, Error> {
+        let name = attribute.as_CFString().to_string();
+        let cached = self.cache.borrow().get(&name).cloned();
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value = match self
+                    .element
+                    .attribute(&AXAttribute::new(attribute.as_CFString()))
+                {
+                    Ok(value) => Ok(value),
+                    // The attribute isn't there, and won't be until something changes.
+                    Err(Error::Ax(
+                        kind @ (AXErrorKind::NoValue | AXErrorKind::AttributeUnsupported),
+                    )) => Err(kind),
+                    // Anything else, e.g. a busy app or a timeout, may go away on the next read.
+                    Err(e) => return Err(e),
+                };
+                self.cache.borrow_mut().insert(name, value.clone());
+                value
+            }
+        }
+        .map_err(Error::Ax)?;
+
+        cast_attribute(value)
+    }
+
+    pub fn set_attribute<T: TCFType>(
+        &self,
+        attribute: &AXAttribute<T>,
+        value: impl Into<T>,
+    ) -> Result<(), Error> {
+        self.cache
+            .borrow_mut()
+            .remove(&attribute.as_CFString().to_string());
+        self.element.set_attribute(attribute, value)
+    }
+}
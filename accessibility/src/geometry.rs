@@ -0,0 +1,162 @@
+use core_graphics_types::geometry::{CGPoint, CGRect};
+
+// AX reports positions with the origin at the top-left of the main display and y growing
+// downwards. Cocoa (NSScreen, NSWindow) puts the origin at the bottom-left of the main display
+// with y growing upwards. Flipping is its own inverse, so each pair below shares a formula; the
+// names just say which way you're going.
+
+/// Converts a point from AX screen coordinates to Cocoa screen coordinates.
+pub fn ax_to_cocoa_point(point: CGPoint, main_display_height: f64) -> CGPoint {
+    CGPoint::new(point.x, main_display_height - point.y)
+}
+
+/// Converts a point from Cocoa screen coordinates to AX screen coordinates.
+pub fn cocoa_to_ax_point(point: CGPoint, main_display_height: f64) -> CGPoint {
+    ax_to_cocoa_point(point, main_display_height)
+}
+
+/// Converts a rect from AX screen coordinates to Cocoa screen coordinates. The origin moves from
+/// the rect's top-left corner to its bottom-left corner.
+pub fn ax_to_cocoa_rect(rect: CGRect, main_display_height: f64) -> CGRect {
+    CGRect::new(
+        &CGPoint::new(
+            rect.origin.x,
+            main_display_height - rect.origin.y - rect.size.height,
+        ),
+        &rect.size,
+    )
+}
+
+/// Converts a rect from Cocoa screen coordinates to AX screen coordinates. The origin moves from
+/// the rect's bottom-left corner to its top-left corner.
+pub fn cocoa_to_ax_rect(rect: CGRect, main_display_height: f64) -> CGRect {
+    ax_to_cocoa_rect(rect, main_display_height)
+}
+
+/// Finds which of `displays` an element with the given frame is on, as an index into
+/// `displays`.
+///
+/// Both `frame` and `displays` are in AX screen coordinates, which is also what
+/// `CGDisplayBounds` returns. The display showing the largest part of the frame wins; a frame
+/// that's entirely off screen goes to the display nearest its center. Returns `None` only if
+/// `displays` is empty.
+pub fn display_for_frame(frame: CGRect, displays: &[CGRect]) -> Option<usize> {
+    let overlap = |display: &CGRect| {
+        let width = (frame.origin.x + frame.size.width).min(display.origin.x + display.size.width)
+            - frame.origin.x.max(display.origin.x);
+        let height = (frame.origin.y + frame.size.height)
+            .min(display.origin.y + display.size.height)
+            - frame.origin.y.max(display.origin.y);
+        width.max(0.0) * height.max(0.0)
+    };
+    let distance = |display: &CGRect| {
+        let dx = (frame.origin.x + frame.size.width / 2.0)
+            - (display.origin.x + display.size.width / 2.0);
+        let dy = (frame.origin.y + frame.size.height / 2.0)
+            - (display.origin.y + display.size.height / 2.0);
+        dx * dx + dy * dy
+    };
+
+    let best = displays
+        .iter()
+        .enumerate()
+        .map(|(i, display)| (i, overlap(display)))
+        .filter(|&(_, area)| area > 0.0)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i);
+
+    best.or_else(|| {
+        displays
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+            .map(|(i, _)| i)
+    })
+}
+
+/// Whether `point` lies inside `rect`. Like `CGRectContainsPoint`, the left and top edges are
+/// inside and the right and bottom edges are not.
+pub fn rect_contains_point(rect: CGRect, point: CGPoint) -> bool {
+    point.x >= rect.origin.x
+        && point.x < rect.origin.x + rect.size.width
+        && point.y >= rect.origin.y
+        && point.y < rect.origin.y + rect.size.height
+}
+
+/// Whether `a` and `b` overlap. Rects that only share an edge don't count.
+pub fn rects_intersect(a: CGRect, b: CGRect) -> bool {
+    a.origin.x < b.origin.x + b.size.width
+        && b.origin.x < a.origin.x + a.size.width
+        && a.origin.y < b.origin.y + b.size.height
+        && b.origin.y < a.origin.y + a.size.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_graphics_types::geometry::CGSize;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> CGRect {
+        CGRect::new(&CGPoint::new(x, y), &CGSize::new(width, height))
+    }
+
+    fn assert_rect_eq(a: CGRect, b: CGRect) {
+        assert_eq!(
+            (a.origin.x, a.origin.y, a.size.width, a.size.height),
+            (b.origin.x, b.origin.y, b.size.width, b.size.height)
+        );
+    }
+
+    #[test]
+    fn point_conversion_round_trips() {
+        let point = CGPoint::new(10.0, 25.0);
+        let cocoa = ax_to_cocoa_point(point, 900.0);
+        assert_eq!((cocoa.x, cocoa.y), (10.0, 875.0));
+
+        let back = cocoa_to_ax_point(cocoa, 900.0);
+        assert_eq!((back.x, back.y), (point.x, point.y));
+    }
+
+    #[test]
+    fn rect_conversion_round_trips() {
+        let frame = rect(10.0, 20.0, 300.0, 200.0);
+        let cocoa = ax_to_cocoa_rect(frame, 900.0);
+        assert_rect_eq(cocoa, rect(10.0, 680.0, 300.0, 200.0));
+        assert_rect_eq(cocoa_to_ax_rect(cocoa, 900.0), frame);
+    }
+
+    #[test]
+    fn display_for_frame_picks_largest_overlap() {
+        let displays = [
+            rect(0.0, 0.0, 1440.0, 900.0),
+            rect(1440.0, 0.0, 1920.0, 1080.0),
+        ];
+
+        // Mostly on the second display.
+        let frame = rect(1340.0, 100.0, 400.0, 300.0);
+        assert_eq!(display_for_frame(frame, &displays), Some(1));
+
+        // Mostly on the first display.
+        let frame = rect(1140.0, 100.0, 400.0, 300.0);
+        assert_eq!(display_for_frame(frame, &displays), Some(0));
+    }
+
+    #[test]
+    fn display_for_frame_off_screen_uses_nearest_display() {
+        let displays = [
+            rect(0.0, 0.0, 1440.0, 900.0),
+            rect(1440.0, 0.0, 1920.0, 1080.0),
+        ];
+
+        let frame = rect(4000.0, 200.0, 100.0, 100.0);
+        assert_eq!(display_for_frame(frame, &displays), Some(1));
+
+        let frame = rect(-500.0, -500.0, 100.0, 100.0);
+        assert_eq!(display_for_frame(frame, &displays), Some(0));
+    }
+
+    #[test]
+    fn display_for_frame_without_displays() {
+        assert_eq!(display_for_frame(rect(0.0, 0.0, 10.0, 10.0), &[]), None);
+    }
+}
@@ -0,0 +1,184 @@
+use accessibility_sys::{
+    kAXApplicationRole, kAXButtonRole, kAXCellRole, kAXCheckBoxRole, kAXCloseButtonSubrole,
+    kAXComboBoxRole, kAXDialogSubrole, kAXFullScreenButtonSubrole, kAXGroupRole, kAXImageRole,
+    kAXListRole, kAXMenuBarItemRole, kAXMenuBarRole, kAXMenuButtonRole, kAXMenuItemRole,
+    kAXMenuRole, kAXMinimizeButtonSubrole, kAXOutlineRole, kAXPopUpButtonRole, kAXPopoverRole,
+    kAXRadioButtonRole, kAXRowRole, kAXScrollAreaRole, kAXSearchFieldSubrole,
+    kAXSecureTextFieldSubrole, kAXSheetRole, kAXSliderRole, kAXStaticTextRole, kAXSwitchSubrole,
+    kAXSystemDialogSubrole, kAXTabGroupRole, kAXTableRole, kAXTextAreaRole, kAXTextFieldRole,
+    kAXToolbarRole, kAXWindowRole, kAXZoomButtonSubrole,
+};
+
+use crate::{AXUIElement, AXUIElementAttributes};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    Application,
+    Window,
+    Dialog,
+    Sheet,
+    Popover,
+    Button,
+    CloseButton,
+    MinimizeButton,
+    ZoomButton,
+    FullScreenButton,
+    MenuButton,
+    PopUpButton,
+    CheckBox,
+    Switch,
+    RadioButton,
+    ComboBox,
+    Slider,
+    TextField,
+    SecureTextField,
+    SearchField,
+    TextArea,
+    StaticText,
+    Image,
+    MenuBar,
+    MenuBarItem,
+    Menu,
+    MenuItem,
+    Toolbar,
+    TabGroup,
+    ScrollArea,
+    List,
+    Table,
+    Outline,
+    Row,
+    Cell,
+    Group,
+    /// Any other role, along with the subrole if there is one.
+    Other(String, Option<String>),
+}
+
+impl ElementKind {
+    #[allow(non_upper_case_globals)]
+    pub fn classify(role: &str, subrole: Option<&str>) -> Self {
+        match (role, subrole) {
+            (kAXApplicationRole, _) => Self::Application,
+            (kAXWindowRole, Some(kAXDialogSubrole | kAXSystemDialogSubrole)) => Self::Dialog,
+            (kAXWindowRole, _) => Self::Window,
+            (kAXSheetRole, _) => Self::Sheet,
+            (kAXPopoverRole, _) => Self::Popover,
+            (kAXButtonRole, Some(kAXCloseButtonSubrole)) => Self::CloseButton,
+            (kAXButtonRole, Some(kAXMinimizeButtonSubrole)) => Self::MinimizeButton,
+            (kAXButtonRole, Some(kAXZoomButtonSubrole)) => Self::ZoomButton,
+            (kAXButtonRole, Some(kAXFullScreenButtonSubrole)) => Self::FullScreenButton,
+            (kAXButtonRole, _) => Self::Button,
+            (kAXMenuButtonRole, _) => Self::MenuButton,
+            (kAXPopUpButtonRole, _) => Self::PopUpButton,
+            (kAXCheckBoxRole, Some(kAXSwitchSubrole)) => Self::Switch,
+            (kAXCheckBoxRole, _) => Self::CheckBox,
+            (kAXRadioButtonRole, _) => Self::RadioButton,
+            (kAXComboBoxRole, _) => Self::ComboBox,
+            (kAXSliderRole, _) => Self::Slider,
+            (kAXTextFieldRole, Some(kAXSecureTextFieldSubrole)) => Self::SecureTextField,
+            (kAXTextFieldRole, Some(kAXSearchFieldSubrole)) => Self::SearchField,
+            (kAXTextFieldRole, _) => Self::TextField,
+            (kAXTextAreaRole, _) => Self::TextArea,
+            (kAXStaticTextRole, _) => Self::StaticText,
+            (kAXImageRole, _) => Self::Image,
+            (kAXMenuBarRole, _) => Self::MenuBar,
+            (kAXMenuBarItemRole, _) => Self::MenuBarItem,
+            (kAXMenuRole, _) => Self::Menu,
+            (kAXMenuItemRole, _) => Self::MenuItem,
+            (kAXToolbarRole, _) => Self::Toolbar,
+            (kAXTabGroupRole, _) => Self::TabGroup,
+            (kAXScrollAreaRole, _) => Self::ScrollArea,
+            (kAXListRole, _) => Self::List,
+            (kAXTableRole, _) => Self::Table,
+            (kAXOutlineRole, _) => Self::Outline,
+            (kAXRowRole, _) => Self::Row,
+            (kAXCellRole, _) => Self::Cell,
+            (kAXGroupRole, _) => Self::Group,
+            (role, subrole) => Self::Other(role.to_owned(), subrole.map(str::to_owned)),
+        }
+    }
+}
+
+impl AXUIElement {
+    pub fn kind(&self) -> ElementKind {
+        let role = self.role().map(|r| r.to_string()).unwrap_or_default();
+        let subrole = self.subrole().ok().map(|s| s.to_string());
+
+        ElementKind::classify(&role, subrole.as_deref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_known_roles() {
+        let cases = [
+            ("AXApplication", None, ElementKind::Application),
+            ("AXWindow", None, ElementKind::Window),
+            ("AXWindow", Some("AXStandardWindow"), ElementKind::Window),
+            ("AXWindow", Some("AXDialog"), ElementKind::Dialog),
+            ("AXWindow", Some("AXSystemDialog"), ElementKind::Dialog),
+            ("AXSheet", None, ElementKind::Sheet),
+            ("AXButton", None, ElementKind::Button),
+            ("AXButton", Some("AXCloseButton"), ElementKind::CloseButton),
+            (
+                "AXButton",
+                Some("AXMinimizeButton"),
+                ElementKind::MinimizeButton,
+            ),
+            ("AXButton", Some("AXZoomButton"), ElementKind::ZoomButton),
+            (
+                "AXButton",
+                Some("AXFullScreenButton"),
+                ElementKind::FullScreenButton,
+            ),
+            ("AXButton", Some("AXToggle"), ElementKind::Button),
+            ("AXCheckBox", None, ElementKind::CheckBox),
+            ("AXCheckBox", Some("AXSwitch"), ElementKind::Switch),
+            ("AXTextField", None, ElementKind::TextField),
+            (
+                "AXTextField",
+                Some("AXSecureTextField"),
+                ElementKind::SecureTextField,
+            ),
+            (
+                "AXTextField",
+                Some("AXSearchField"),
+                ElementKind::SearchField,
+            ),
+            ("AXStaticText", None, ElementKind::StaticText),
+            ("AXMenuBarItem", None, ElementKind::MenuBarItem),
+            ("AXRow", Some("AXOutlineRow"), ElementKind::Row),
+            ("AXGroup", None, ElementKind::Group),
+        ];
+
+        for (role, subrole, kind) in cases.iter() {
+            assert_eq!(
+                ElementKind::classify(role, *subrole),
+                *kind,
+                "{} / {:?}",
+                role,
+                subrole
+            );
+        }
+    }
+
+    #[test]
+    fn classify_unknown_roles() {
+        let cases = [
+            ("AXLevelIndicator", None),
+            ("AXLevelIndicator", Some("AXRatingIndicator")),
+            ("", None),
+            // Subroles only refine the role they belong to.
+            ("AXUnknown", Some("AXCloseButton")),
+        ];
+
+        for (role, subrole) in cases.iter() {
+            assert_eq!(
+                ElementKind::classify(role, *subrole),
+                ElementKind::Other(role.to_string(), subrole.map(str::to_owned)),
+            );
+        }
+    }
+}
@@ -1,10 +1,26 @@
 pub mod action;
+pub mod application;
 pub mod attribute;
+pub mod cache;
+pub mod geometry;
+pub mod kind;
+pub mod notification;
+pub mod observer;
+pub mod permissions;
+pub mod snapshot;
+pub mod text_marker;
 pub mod ui_element;
 mod util;
 pub mod value;
 
-use accessibility_sys::{error_string, AXError};
+use accessibility_sys::{
+    kAXErrorAPIDisabled, kAXErrorActionUnsupported, kAXErrorAttributeUnsupported,
+    kAXErrorCannotComplete, kAXErrorFailure, kAXErrorIllegalArgument, kAXErrorInvalidUIElement,
+    kAXErrorInvalidUIElementObserver, kAXErrorNoValue, kAXErrorNotEnoughPrecision,
+    kAXErrorNotImplemented, kAXErrorNotificationAlreadyRegistered,
+    kAXErrorNotificationNotRegistered, kAXErrorNotificationUnsupported,
+    kAXErrorParameterizedAttributeUnsupported, AXError,
+};
 use core_foundation::{
     array::CFArray,
     base::CFTypeID,
@@ -12,44 +28,189 @@ use core_foundation::{
     string::CFString,
 };
 use std::{
-    cell::{Cell, RefCell},
+    cell::RefCell,
+    collections::{BTreeMap, HashSet, VecDeque},
     thread,
     time::{Duration, Instant},
 };
 use thiserror::Error as TError;
 
 pub use action::*;
+pub use application::*;
 pub use attribute::*;
+pub use cache::*;
+pub use kind::*;
+pub use notification::*;
+pub use observer::*;
 pub use ui_element::*;
 
-#[derive(Debug, TError)]
+#[derive(Clone, Debug, TError)]
 pub enum Error {
     #[error("element not found")]
     NotFound,
-    #[error(
-        "expected attribute type {} but got {}",
-        type_name(*expected),
-        type_name(*received),
-    )]
+    #[error("{}", describe_type_mismatch(*expected, *received))]
     UnexpectedType {
         expected: CFTypeID,
         received: CFTypeID,
     },
-    #[error("accessibility error {}", error_string(*.0))]
-    Ax(AXError),
+    #[error("number can't be represented as the requested type")]
+    UnconvertibleNumber,
+    #[error("accessibility error: {0}")]
+    Ax(AXErrorKind),
+    #[error("timed out")]
+    TimedOut,
+    #[error("could not create keyboard events to post")]
+    EventPost,
+    #[error("the operation was cancelled")]
+    Cancelled,
+}
+
+impl From<value::WrongType> for Error {
+    fn from(e: value::WrongType) -> Self {
+        Error::UnexpectedType {
+            expected: e.expected,
+            received: e.received,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, TError)]
+pub enum AXErrorKind {
+    #[error("a system error occurred")]
+    Failure,
+    #[error("illegal argument")]
+    IllegalArgument,
+    #[error("invalid UI element")]
+    InvalidUIElement,
+    #[error("invalid observer")]
+    InvalidUIElementObserver,
+    #[error("cannot complete; the application may be busy or unresponsive")]
+    CannotComplete,
+    #[error("attribute unsupported")]
+    AttributeUnsupported,
+    #[error("action unsupported")]
+    ActionUnsupported,
+    #[error("notification unsupported")]
+    NotificationUnsupported,
+    #[error("not implemented")]
+    NotImplemented,
+    #[error("notification already registered")]
+    NotificationAlreadyRegistered,
+    #[error("notification not registered")]
+    NotificationNotRegistered,
+    #[error("accessibility API disabled")]
+    APIDisabled,
+    #[error("no value")]
+    NoValue,
+    #[error("parameterized attribute unsupported")]
+    ParameterizedAttributeUnsupported,
+    #[error("not enough precision")]
+    NotEnoughPrecision,
+    #[error("unknown error code {0}")]
+    Unknown(AXError),
+}
+
+impl AXErrorKind {
+    pub fn code(&self) -> AXError {
+        match self {
+            Self::Failure => kAXErrorFailure,
+            Self::IllegalArgument => kAXErrorIllegalArgument,
+            Self::InvalidUIElement => kAXErrorInvalidUIElement,
+            Self::InvalidUIElementObserver => kAXErrorInvalidUIElementObserver,
+            Self::CannotComplete => kAXErrorCannotComplete,
+            Self::AttributeUnsupported => kAXErrorAttributeUnsupported,
+            Self::ActionUnsupported => kAXErrorActionUnsupported,
+            Self::NotificationUnsupported => kAXErrorNotificationUnsupported,
+            Self::NotImplemented => kAXErrorNotImplemented,
+            Self::NotificationAlreadyRegistered => kAXErrorNotificationAlreadyRegistered,
+            Self::NotificationNotRegistered => kAXErrorNotificationNotRegistered,
+            Self::APIDisabled => kAXErrorAPIDisabled,
+            Self::NoValue => kAXErrorNoValue,
+            Self::ParameterizedAttributeUnsupported => kAXErrorParameterizedAttributeUnsupported,
+            Self::NotEnoughPrecision => kAXErrorNotEnoughPrecision,
+            Self::Unknown(code) => *code,
+        }
+    }
+}
+
+impl From<AXError> for AXErrorKind {
+    #[allow(non_upper_case_globals)]
+    fn from(code: AXError) -> Self {
+        match code {
+            kAXErrorFailure => Self::Failure,
+            kAXErrorIllegalArgument => Self::IllegalArgument,
+            kAXErrorInvalidUIElement => Self::InvalidUIElement,
+            kAXErrorInvalidUIElementObserver => Self::InvalidUIElementObserver,
+            kAXErrorCannotComplete => Self::CannotComplete,
+            kAXErrorAttributeUnsupported => Self::AttributeUnsupported,
+            kAXErrorActionUnsupported => Self::ActionUnsupported,
+            kAXErrorNotificationUnsupported => Self::NotificationUnsupported,
+            kAXErrorNotImplemented => Self::NotImplemented,
+            kAXErrorNotificationAlreadyRegistered => Self::NotificationAlreadyRegistered,
+            kAXErrorNotificationNotRegistered => Self::NotificationNotRegistered,
+            kAXErrorAPIDisabled => Self::APIDisabled,
+            kAXErrorNoValue => Self::NoValue,
+            kAXErrorParameterizedAttributeUnsupported => Self::ParameterizedAttributeUnsupported,
+            kAXErrorNotEnoughPrecision => Self::NotEnoughPrecision,
+            code => Self::Unknown(code),
+        }
+    }
+}
+
+/// Runs `f` up to `attempts` times while it fails with `CannotComplete`, which usually just means
+/// the app was busy. The delay between attempts starts at `delay` and doubles each time.
+///
+/// `f` always runs at least once, so an `attempts` of 0 behaves like 1.
+pub fn retry<T, F>(attempts: usize, delay: Duration, mut f: F) -> Result<T, Error>
+where
+    F: FnMut() -> Result<T, Error>,
+{
+    let mut delay = delay;
+
+    for _ in 1..attempts {
+        match f() {
+            Err(Error::Ax(AXErrorKind::CannotComplete)) => {
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+            }
+            result => return result,
+        }
+    }
+    f()
 }
 
 fn type_name(type_id: CFTypeID) -> CFString {
     unsafe { CFString::wrap_under_create_rule(CFCopyTypeIDDescription(type_id)) }
 }
 
+fn describe_type_mismatch(expected: CFTypeID, received: CFTypeID) -> String {
+    if expected == received {
+        format!(
+            "{} value does not hold the expected kind of data",
+            type_name(expected)
+        )
+    } else {
+        format!(
+            "expected attribute type {} but got {}",
+            type_name(expected),
+            type_name(received)
+        )
+    }
+}
+
 pub trait TreeVisitor {
-    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow;
-    fn exit_element(&self, element: &AXUIElement);
+    fn enter_element(&self, element: &AXUIElement, depth: usize) -> TreeWalkerFlow;
+    fn exit_element(&self, element: &AXUIElement, depth: usize);
+
+    /// Called when `element`'s children can't be read, so its subtree is missing from the walk.
+    fn on_error(&self, _element: &AXUIElement, _error: &Error) {}
 }
 
 pub struct TreeWalker {
     attr_children: AXAttribute<CFArray<AXUIElement>>,
+    attr_visible_children: Option<AXAttribute<CFArray<AXUIElement>>>,
+    max_depth: Option<usize>,
+    abort_on_error: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -61,41 +222,253 @@ pub enum TreeWalkerFlow {
 
 impl TreeWalker {
     pub fn new() -> Self {
-        Self {
-            attr_children: AXAttribute::children(),
-        }
+        TreeWalkerBuilder::default().build()
+    }
+
+    pub fn builder() -> TreeWalkerBuilder {
+        TreeWalkerBuilder::default()
     }
 
     pub fn walk(&self, root: &AXUIElement, visitor: &dyn TreeVisitor) {
-        let _ = self.walk_one(root, visitor);
+        let _ = self.walk_one(root, 0, visitor);
     }
 
-    fn walk_one(&self, root: &AXUIElement, visitor: &dyn TreeVisitor) -> TreeWalkerFlow {
-        let mut flow = visitor.enter_element(root);
+    fn walk_one(
+        &self,
+        root: &AXUIElement,
+        depth: usize,
+        visitor: &dyn TreeVisitor,
+    ) -> TreeWalkerFlow {
+        let mut flow = visitor.enter_element(root, depth);
 
-        if flow == TreeWalkerFlow::Continue {
-            if let Ok(children) = root.attribute(&self.attr_children) {
-                for child in children.into_iter() {
-                    let child_flow = self.walk_one(&*child, visitor);
+        if flow == TreeWalkerFlow::Continue && self.max_depth.map_or(true, |max| depth < max) {
+            match self.children(root) {
+                Ok(children) => {
+                    for child in children.into_iter() {
+                        let child_flow = self.walk_one(&*child, depth + 1, visitor);
 
-                    if child_flow == TreeWalkerFlow::Exit {
-                        flow = child_flow;
-                        break;
+                        if child_flow == TreeWalkerFlow::Exit {
+                            flow = child_flow;
+                            break;
+                        }
+                    }
+                }
+                // Leaves commonly report these instead of an empty list.
+                Err(Error::Ax(AXErrorKind::AttributeUnsupported | AXErrorKind::NoValue)) => {}
+                Err(e) => {
+                    visitor.on_error(root, &e);
+
+                    if self.abort_on_error {
+                        flow = TreeWalkerFlow::Exit;
                     }
                 }
             }
         }
 
-        visitor.exit_element(root);
+        visitor.exit_element(root, depth);
         flow
     }
+
+    fn children(&self, element: &AXUIElement) -> Result<CFArray<AXUIElement>, Error> {
+        if let Some(attr_visible_children) = &self.attr_visible_children {
+            match element.attribute(attr_visible_children) {
+                // Only lists, tables and the like report visible children; fall back for the rest.
+                Err(Error::Ax(AXErrorKind::AttributeUnsupported | AXErrorKind::NoValue)) => {}
+                result => return result,
+            }
+        }
+        element.attribute(&self.attr_children)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TreeWalkerBuilder {
+    max_depth: Option<usize>,
+    visible_children: bool,
+    abort_on_error: bool,
+}
+
+impl TreeWalkerBuilder {
+    /// Don't descend below `max_depth`; the root is at depth 0.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Walk `visible_children` where an element has them, and `children` elsewhere.
+    pub fn visible_children(mut self, visible_children: bool) -> Self {
+        self.visible_children = visible_children;
+        self
+    }
+
+    /// Stop the whole walk when an element's children can't be read, instead of skipping it.
+    pub fn abort_on_error(mut self, abort_on_error: bool) -> Self {
+        self.abort_on_error = abort_on_error;
+        self
+    }
+
+    pub fn build(self) -> TreeWalker {
+        TreeWalker {
+            attr_children: AXAttribute::children(),
+            attr_visible_children: self.visible_children.then(AXAttribute::visible_children),
+            max_depth: self.max_depth,
+            abort_on_error: self.abort_on_error,
+        }
+    }
+}
+
+/// Collects every element the predicate accepts during a walk, in the order they were visited.
+pub struct CollectVisitor {
+    predicate: Box<dyn Fn(&AXUIElement) -> bool>,
+    elements: RefCell<Vec<AXUIElement>>,
+}
+
+impl CollectVisitor {
+    pub fn new<F>(predicate: F) -> Self
+    where
+        F: 'static + Fn(&AXUIElement) -> bool,
+    {
+        Self {
+            predicate: Box::new(predicate),
+            elements: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn into_elements(self) -> Vec<AXUIElement> {
+        self.elements.into_inner()
+    }
+}
+
+impl TreeVisitor for CollectVisitor {
+    fn enter_element(&self, element: &AXUIElement, _depth: usize) -> TreeWalkerFlow {
+        if (self.predicate)(element) {
+            self.elements.borrow_mut().push(element.clone());
+        }
+
+        TreeWalkerFlow::Continue
+    }
+
+    fn exit_element(&self, _element: &AXUIElement, _depth: usize) {}
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TreeStats {
+    /// Element counts keyed by role; elements without a role are counted under `""`.
+    pub roles: BTreeMap<String, usize>,
+    pub total: usize,
+    /// Depth of the deepest element visited, with the root at 0.
+    pub max_depth: usize,
+}
+
+/// Tallies the shape of the tree during a walk; read the results with `stats` afterwards.
+#[derive(Default)]
+pub struct StatsVisitor {
+    stats: RefCell<TreeStats>,
+}
+
+impl StatsVisitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stats(&self) -> TreeStats {
+        self.stats.borrow().clone()
+    }
+}
+
+impl TreeVisitor for StatsVisitor {
+    fn enter_element(&self, element: &AXUIElement, depth: usize) -> TreeWalkerFlow {
+        let role = element.role().map(|r| r.to_string()).unwrap_or_default();
+        let mut stats = self.stats.borrow_mut();
+
+        *stats.roles.entry(role).or_default() += 1;
+        stats.total += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+
+        TreeWalkerFlow::Continue
+    }
+
+    fn exit_element(&self, _element: &AXUIElement, _depth: usize) {}
+}
+
+pub struct Descendants {
+    stack: Vec<(AXUIElement, usize)>,
+    max_depth: Option<usize>,
+    attr_children: AXAttribute<CFArray<AXUIElement>>,
+}
+
+impl Descendants {
+    pub(crate) fn new(root: &AXUIElement) -> Self {
+        Self {
+            stack: vec![(root.clone(), 0)],
+            max_depth: None,
+            attr_children: AXAttribute::children(),
+        }
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+}
+
+impl Iterator for Descendants {
+    type Item = AXUIElement;
+
+    fn next(&mut self) -> Option<AXUIElement> {
+        loop {
+            let (element, depth) = self.stack.pop()?;
+
+            if self.max_depth.map_or(true, |max| depth < max) {
+                if let Ok(children) = element.attribute(&self.attr_children) {
+                    let children: Vec<AXUIElement> = children.iter().map(|c| c.clone()).collect();
+                    // Reversed so that children come off the stack in order.
+                    self.stack
+                        .extend(children.into_iter().rev().map(|child| (child, depth + 1)));
+                }
+            }
+
+            // The root itself isn't one of its descendants.
+            if depth > 0 {
+                return Some(element);
+            }
+        }
+    }
+}
+
+pub struct Ancestors {
+    current: Option<AXUIElement>,
+    visited: HashSet<AXUIElement>,
+}
+
+impl Ancestors {
+    pub(crate) fn new(element: &AXUIElement) -> Self {
+        Self {
+            current: Some(element.clone()),
+            visited: HashSet::from([element.clone()]),
+        }
+    }
+}
+
+impl Iterator for Ancestors {
+    type Item = AXUIElement;
+
+    fn next(&mut self) -> Option<AXUIElement> {
+        let parent = self.current.take()?.parent().ok()?;
+
+        if !self.visited.insert(parent.clone()) {
+            return None;
+        }
+
+        self.current = Some(parent.clone());
+        Some(parent)
+    }
 }
 
 pub struct ElementFinder {
     root: AXUIElement,
     implicit_wait: Option<Duration>,
     predicate: Box<dyn Fn(&AXUIElement) -> bool>,
-    depth: Cell<usize>,
     cached: RefCell<Option<AXUIElement>>,
 }
 
@@ -108,7 +481,6 @@ impl ElementFinder {
             root: root.clone(),
             predicate: Box::new(predicate),
             implicit_wait,
-            depth: Cell::new(0),
             cached: RefCell::new(None),
         }
     }
@@ -146,10 +518,133 @@ impl ElementFinder {
         self.cached.replace(None);
     }
 
+    pub fn find_first<F>(root: &AXUIElement, predicate: F) -> Option<AXUIElement>
+    where
+        F: Fn(&AXUIElement) -> bool,
+    {
+        Self::find_first_with_limits(root, predicate, SearchLimits::default())
+    }
+
+    pub fn find_first_with_limits<F>(
+        root: &AXUIElement,
+        predicate: F,
+        limits: SearchLimits,
+    ) -> Option<AXUIElement>
+    where
+        F: Fn(&AXUIElement) -> bool,
+    {
+        Self::search(root, &predicate, limits, true).pop()
+    }
+
+    /// Searches `root` until an element matches `predicate`, giving up with `Error::TimedOut`
+    /// after `timeout`.
+    pub fn wait_for<F>(
+        root: &AXUIElement,
+        predicate: F,
+        timeout: Duration,
+    ) -> Result<AXUIElement, Error>
+    where
+        F: Fn(&AXUIElement) -> bool,
+    {
+        util::poll_until(timeout, Duration::from_millis(250), || {
+            Self::find_first(root, &predicate).map(Ok)
+        })
+    }
+
+    pub fn find_all<F>(root: &AXUIElement, predicate: F) -> Vec<AXUIElement>
+    where
+        F: Fn(&AXUIElement) -> bool,
+    {
+        Self::find_all_with_limits(root, predicate, SearchLimits::default())
+    }
+
+    pub fn find_all_with_limits<F>(
+        root: &AXUIElement,
+        predicate: F,
+        limits: SearchLimits,
+    ) -> Vec<AXUIElement>
+    where
+        F: Fn(&AXUIElement) -> bool,
+    {
+        Self::search(root, &predicate, limits, false)
+    }
+
+    pub fn find_by_role(root: &AXUIElement, role: &str) -> Option<AXUIElement> {
+        Self::find_first(root, |element| element.role().is_ok_and(|r| r == role))
+    }
+
+    pub fn find_all_by_role(root: &AXUIElement, role: &str) -> Vec<AXUIElement> {
+        Self::find_all(root, |element| element.role().is_ok_and(|r| r == role))
+    }
+
+    /// Searches breadth-first starting from `root` itself, so the match closest to the root wins.
+    pub fn find_by_identifier(root: &AXUIElement, identifier: &str) -> Option<AXUIElement> {
+        Self::find_first(root, |element| {
+            element.identifier().is_ok_and(|id| id == identifier)
+        })
+    }
+
+    pub fn find_by_attribute<T: TCFType>(
+        root: &AXUIElement,
+        attribute: &AXAttribute<T>,
+        value: &T,
+    ) -> Option<AXUIElement> {
+        let value = value.as_CFType();
+
+        Self::find_first(root, |element| {
+            element
+                .attribute(attribute)
+                .is_ok_and(|v| v.as_CFType() == value)
+        })
+    }
+
+    fn search(
+        root: &AXUIElement,
+        predicate: &dyn Fn(&AXUIElement) -> bool,
+        limits: SearchLimits,
+        first_only: bool,
+    ) -> Vec<AXUIElement> {
+        let attr_children = AXAttribute::children();
+        let mut queue = VecDeque::from([(root.clone(), 0)]);
+        let mut visited = 0;
+        let mut found = Vec::new();
+
+        while let Some((element, depth)) = queue.pop_front() {
+            if visited >= limits.max_nodes {
+                break;
+            }
+            visited += 1;
+
+            if predicate(&element) {
+                found.push(element.clone());
+
+                if first_only {
+                    break;
+                }
+            }
+
+            if depth < limits.max_depth {
+                if let Ok(children) = element.attribute(&attr_children) {
+                    queue.extend(children.iter().map(|child| (child.clone(), depth + 1)));
+                }
+            }
+        }
+
+        found
+    }
+
     pub fn attribute<T: TCFType>(&self, attribute: &AXAttribute<T>) -> Result<T, Error> {
         self.find()?.attribute(attribute)
     }
 
+    pub fn parameterized_attribute<P: TCFType, T: TCFType>(
+        &self,
+        attribute: &AXParameterizedAttribute<P, T>,
+        parameter: impl Into<P>,
+    ) -> Result<T, Error> {
+        self.find()?.parameterized_attribute(attribute, parameter)
+    }
+
     pub fn set_attribute<T: TCFType>(
         &self,
         attribute: &AXAttribute<T>,
@@ -164,24 +659,151 @@ impl ElementFinder {
 }
 
 const MAX_DEPTH: usize = 100;
+const MAX_NODES: usize = 10_000;
 
-impl TreeVisitor for ElementFinder {
-    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
-        self.depth.set(self.depth.get() + 1);
+#[derive(Copy, Clone, Debug)]
+pub struct SearchLimits {
+    pub max_depth: usize,
+    pub max_nodes: usize,
+}
 
+impl Default for SearchLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: MAX_DEPTH,
+            max_nodes: MAX_NODES,
+        }
+    }
+}
+
+impl TreeVisitor for ElementFinder {
+    fn enter_element(&self, element: &AXUIElement, depth: usize) -> TreeWalkerFlow {
         if (self.predicate)(element) {
             self.cached.replace(Some(element.clone()));
             return TreeWalkerFlow::Exit;
         }
 
-        if self.depth.get() > MAX_DEPTH {
+        if depth >= MAX_DEPTH {
             TreeWalkerFlow::SkipSubtree
         } else {
             TreeWalkerFlow::Continue
         }
     }
 
-    fn exit_element(&self, _element: &AXUIElement) {
-        self.depth.set(self.depth.get() - 1)
+    fn exit_element(&self, _element: &AXUIElement, _depth: usize) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_element as element;
+
+    type Step = Result<u32, AXErrorKind>;
+
+    // Runs `retry` over scripted results, repeating the last one once the script runs out.
+    // Returns the outcome and how many times `retry` called back.
+    fn run_retry(attempts: usize, script: &[Step]) -> (Step, usize) {
+        let mut calls = 0;
+        let result = retry(attempts, Duration::ZERO, || {
+            let step = script[calls.min(script.len() - 1)];
+            calls += 1;
+            step.map_err(Error::Ax)
+        });
+        let result = result.map_err(|e| match e {
+            Error::Ax(kind) => kind,
+            e => panic!("unexpected error {}", e),
+        });
+        (result, calls)
+    }
+
+    #[test]
+    fn retry_only_repeats_cannot_complete() {
+        use AXErrorKind::{CannotComplete, InvalidUIElement};
+
+        let cases: &[(usize, &[Step], Step, usize)] = &[
+            (
+                5,
+                &[Err(CannotComplete), Err(CannotComplete), Ok(7)],
+                Ok(7),
+                3,
+            ),
+            (3, &[Err(CannotComplete)], Err(CannotComplete), 3),
+            (3, &[Err(InvalidUIElement), Ok(7)], Err(InvalidUIElement), 1),
+            // `f` runs at least once, even with no attempts.
+            (0, &[Err(CannotComplete)], Err(CannotComplete), 1),
+        ];
+
+        for (attempts, script, result, calls) in cases.iter() {
+            assert_eq!(
+                run_retry(*attempts, script),
+                (*result, *calls),
+                "{} attempts of {:?}",
+                attempts,
+                script
+            );
+        }
+    }
+
+    // Feeds `visitor` a flat sequence of elements, checking that it keeps the walk going.
+    fn visit(visitor: &dyn TreeVisitor, elements: &[(AXUIElement, usize)]) {
+        for (element, depth) in elements {
+            assert!(visitor.enter_element(element, *depth) == TreeWalkerFlow::Continue);
+            visitor.exit_element(element, *depth);
+        }
+    }
+
+    #[test]
+    fn collect_visitor_keeps_matches_in_visit_order() {
+        let visitor = CollectVisitor::new(|element| element.pid().is_ok_and(|pid| pid % 2 == 1));
+        let elements: Vec<_> = (1..=5).map(|pid| (element(pid), pid as usize)).collect();
+        visit(&visitor, &elements);
+
+        assert_eq!(
+            visitor.into_elements(),
+            [element(1), element(3), element(5)]
+        );
+    }
+
+    #[test]
+    fn collect_visitor_with_no_matches() {
+        let visitor = CollectVisitor::new(|_| false);
+        visit(&visitor, &[(element(1), 0)]);
+
+        assert!(visitor.into_elements().is_empty());
+    }
+
+    #[test]
+    fn stats_visitor_counts_roles_and_depth() {
+        // Whether these have a readable role depends on the process's accessibility permissions,
+        // so the expected buckets are worked out from whatever they report. Elements without a
+        // role, like the one for a pid nothing runs as, count under "".
+        let elements = [
+            (AXUIElement::system_wide(), 0),
+            (element(i32::MAX), 1),
+            (AXUIElement::system_wide(), 3),
+            (element(i32::MAX), 2),
+        ];
+        let visitor = StatsVisitor::new();
+        visit(&visitor, &elements);
+
+        let mut roles = BTreeMap::new();
+        for (element, _) in &elements {
+            let role = element.role().map(|r| r.to_string()).unwrap_or_default();
+            *roles.entry(role).or_insert(0) += 1;
+        }
+        assert!(roles.contains_key(""));
+        assert_eq!(
+            visitor.stats(),
+            TreeStats {
+                roles,
+                total: 4,
+                max_depth: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn stats_visitor_starts_empty() {
+        assert_eq!(StatsVisitor::new().stats(), TreeStats::default());
     }
 }
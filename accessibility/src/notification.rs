@@ -0,0 +1,107 @@
+use accessibility_sys::{
+    kAXAnnouncementRequestedNotification, kAXApplicationActivatedNotification,
+    kAXApplicationDeactivatedNotification, kAXApplicationHiddenNotification,
+    kAXApplicationShownNotification, kAXCreatedNotification, kAXDrawerCreatedNotification,
+    kAXElementBusyChangedNotification, kAXFocusedUIElementChangedNotification,
+    kAXFocusedWindowChangedNotification, kAXHelpTagCreatedNotification,
+    kAXLayoutChangedNotification, kAXMainWindowChangedNotification, kAXMenuClosedNotification,
+    kAXMenuItemSelectedNotification, kAXMenuOpenedNotification, kAXMovedNotification,
+    kAXResizedNotification, kAXRowCollapsedNotification, kAXRowCountChangedNotification,
+    kAXRowExpandedNotification, kAXSelectedCellsChangedNotification,
+    kAXSelectedChildrenChangedNotification, kAXSelectedChildrenMovedNotification,
+    kAXSelectedColumnsChangedNotification, kAXSelectedRowsChangedNotification,
+    kAXSelectedTextChangedNotification, kAXSheetCreatedNotification, kAXTitleChangedNotification,
+    kAXUIElementDestroyedNotification, kAXUnitsChangedNotification, kAXValueChangedNotification,
+    kAXWindowCreatedNotification, kAXWindowDeminiaturizedNotification,
+    kAXWindowMiniaturizedNotification, kAXWindowMovedNotification, kAXWindowResizedNotification,
+};
+use core_foundation::string::CFString;
+
+macro_rules! define_notifications {
+    ($(($variant:ident, $const:ident)),* $(,)?) => {
+        /// A notification an observer can register for. `Other` covers custom notifications and
+        /// any this crate doesn't know about.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum Notification {
+            $($variant,)*
+            Other(CFString),
+        }
+
+        impl Notification {
+            pub fn as_cfstring(&self) -> CFString {
+                match self {
+                    $(Self::$variant => CFString::from_static_string($const),)*
+                    Self::Other(name) => name.clone(),
+                }
+            }
+        }
+
+        impl From<CFString> for Notification {
+            #[allow(non_upper_case_globals)]
+            fn from(name: CFString) -> Self {
+                match &*name.to_string() {
+                    $($const => Self::$variant,)*
+                    _ => Self::Other(name),
+                }
+            }
+        }
+    };
+}
+
+define_notifications![
+    (MainWindowChanged, kAXMainWindowChangedNotification),
+    (FocusedWindowChanged, kAXFocusedWindowChangedNotification),
+    (
+        FocusedUIElementChanged,
+        kAXFocusedUIElementChangedNotification
+    ),
+    (ApplicationActivated, kAXApplicationActivatedNotification),
+    (
+        ApplicationDeactivated,
+        kAXApplicationDeactivatedNotification
+    ),
+    (ApplicationHidden, kAXApplicationHiddenNotification),
+    (ApplicationShown, kAXApplicationShownNotification),
+    (WindowCreated, kAXWindowCreatedNotification),
+    (WindowMoved, kAXWindowMovedNotification),
+    (WindowResized, kAXWindowResizedNotification),
+    (WindowMiniaturized, kAXWindowMiniaturizedNotification),
+    (WindowDeminiaturized, kAXWindowDeminiaturizedNotification),
+    (DrawerCreated, kAXDrawerCreatedNotification),
+    (SheetCreated, kAXSheetCreatedNotification),
+    (HelpTagCreated, kAXHelpTagCreatedNotification),
+    (ValueChanged, kAXValueChangedNotification),
+    (UIElementDestroyed, kAXUIElementDestroyedNotification),
+    (ElementBusyChanged, kAXElementBusyChangedNotification),
+    (MenuOpened, kAXMenuOpenedNotification),
+    (MenuClosed, kAXMenuClosedNotification),
+    (MenuItemSelected, kAXMenuItemSelectedNotification),
+    (RowCountChanged, kAXRowCountChangedNotification),
+    (RowExpanded, kAXRowExpandedNotification),
+    (RowCollapsed, kAXRowCollapsedNotification),
+    (SelectedCellsChanged, kAXSelectedCellsChangedNotification),
+    (UnitsChanged, kAXUnitsChangedNotification),
+    (SelectedChildrenMoved, kAXSelectedChildrenMovedNotification),
+    (
+        SelectedChildrenChanged,
+        kAXSelectedChildrenChangedNotification
+    ),
+    (Resized, kAXResizedNotification),
+    (Moved, kAXMovedNotification),
+    (Created, kAXCreatedNotification),
+    (SelectedRowsChanged, kAXSelectedRowsChangedNotification),
+    (
+        SelectedColumnsChanged,
+        kAXSelectedColumnsChangedNotification
+    ),
+    (SelectedTextChanged, kAXSelectedTextChangedNotification),
+    (TitleChanged, kAXTitleChangedNotification),
+    (LayoutChanged, kAXLayoutChangedNotification),
+    (AnnouncementRequested, kAXAnnouncementRequestedNotification),
+];
+
+impl From<&CFString> for Notification {
+    fn from(name: &CFString) -> Self {
+        name.clone().into()
+    }
+}
@@ -0,0 +1,725 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    ffi::c_void,
+    ops::Deref,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use accessibility_sys::{
+    kAXFocusedUIElementChangedNotification, kAXFocusedWindowChangedNotification,
+    kAXUIElementDestroyedNotification, kAXValueChangedNotification, kAXWindowCreatedNotification,
+    kAXWindowMovedNotification, kAXWindowResizedNotification, pid_t, AXObserverAddNotification,
+    AXObserverCreate, AXObserverGetRunLoopSource, AXObserverRef, AXObserverRemoveNotification,
+    AXUIElementRef,
+};
+use core_foundation::{
+    base::{CFRelease, CFType, TCFType},
+    runloop::{kCFRunLoopDefaultMode, CFRunLoop, CFRunLoopSource, CFRunLoopSourceInvalidate},
+    string::{CFString, CFStringRef},
+};
+
+use crate::{
+    util::{ax_call, ax_call_void},
+    AXErrorKind, AXUIElement, AXUIElementAttributes, Error, Notification, SendableElement,
+};
+
+type Callback = Box<dyn FnMut(AXUIElement, CFString)>;
+
+pub struct AXObserver {
+    observer: AXObserverRef,
+    callback: *mut Callback,
+}
+
+impl AXObserver {
+    pub fn new<F>(pid: pid_t, callback: F) -> Result<Self, Error>
+    where
+        F: 'static + FnMut(AXUIElement, CFString),
+    {
+        let observer =
+            unsafe { ax_call(|x| AXObserverCreate(pid, observer_callback, x)).map_err(Error::Ax)? };
+        let callback: Callback = Box::new(callback);
+
+        Ok(Self {
+            observer,
+            callback: Box::into_raw(Box::new(callback)),
+        })
+    }
+
+    pub fn add_notification(
+        &self,
+        element: &AXUIElement,
+        notification: impl Into<Notification>,
+    ) -> Result<(), Error> {
+        let notification = notification.into().as_cfstring();
+        unsafe {
+            ax_call_void(|| {
+                AXObserverAddNotification(
+                    self.observer,
+                    element.as_concrete_TypeRef(),
+                    notification.as_concrete_TypeRef(),
+                    self.callback as *mut c_void,
+                )
+            })
+            .map_err(Error::Ax)
+        }
+    }
+
+    pub fn remove_notification(
+        &self,
+        element: &AXUIElement,
+        notification: impl Into<Notification>,
+    ) -> Result<(), Error> {
+        let notification = notification.into().as_cfstring();
+        unsafe {
+            ax_call_void(|| {
+                AXObserverRemoveNotification(
+                    self.observer,
+                    element.as_concrete_TypeRef(),
+                    notification.as_concrete_TypeRef(),
+                )
+            })
+            .map_err(Error::Ax)
+        }
+    }
+
+    pub fn run_loop_source(&self) -> CFRunLoopSource {
+        unsafe { CFRunLoopSource::wrap_under_get_rule(AXObserverGetRunLoopSource(self.observer)) }
+    }
+}
+
+impl Drop for AXObserver {
+    fn drop(&mut self) {
+        unsafe {
+            // The run loop may still hold the source (and thus the observer), so make sure it
+            // can't call back into the closure we're about to free.
+            CFRunLoopSourceInvalidate(AXObserverGetRunLoopSource(self.observer));
+            CFRelease(self.observer as *const c_void);
+            drop(Box::from_raw(self.callback));
+        }
+    }
+}
+
+unsafe extern "C" fn observer_callback(
+    _observer: AXObserverRef,
+    element: AXUIElementRef,
+    notification: CFStringRef,
+    refcon: *mut c_void,
+) {
+    let callback = &mut *(refcon as *mut Callback);
+
+    callback(
+        AXUIElement::wrap_under_get_rule(element),
+        CFString::wrap_under_get_rule(notification),
+    );
+}
+
+pub struct AXNotification {
+    pub element: AXUIElement,
+    pub notification: CFString,
+}
+
+// The AX and CF objects themselves can be used from any thread; it's only the wrappers that
+// don't say so.
+unsafe impl Send for AXNotification {}
+
+pub struct NotificationStream {
+    // Only detached streams keep a sender; a live stream's channel must disconnect when its
+    // observer thread exits.
+    #[cfg(feature = "test-support")]
+    sender: Option<mpsc::Sender<AXNotification>>,
+    receiver: Receiver<AXNotification>,
+    // `None` for detached streams, which have no run loop of their own to stop.
+    run_loop: Option<CFRunLoop>,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl NotificationStream {
+    pub fn new(
+        pid: pid_t,
+        element: &AXUIElement,
+        notifications: &[Notification],
+    ) -> Result<Self, Error> {
+        let (sender, receiver) = mpsc::channel();
+        let (setup_sender, setup_receiver) = mpsc::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+        let thread_sender = sender;
+        let element = SendableElement::new(element.clone());
+        // CFStrings can't cross threads, so hand the names over as Rust strings.
+        let notifications: Vec<String> = notifications
+            .iter()
+            .map(|n| n.as_cfstring().to_string())
+            .collect();
+
+        let thread = thread::spawn(move || {
+            let element = element.into_inner();
+            let run_loop = CFRunLoop::get_current();
+            let callback_stopped = thread_stopped.clone();
+            let callback_run_loop = run_loop.clone();
+
+            let observer = AXObserver::new(pid, move |element, notification| {
+                if thread_sender
+                    .send(AXNotification {
+                        element,
+                        notification,
+                    })
+                    .is_err()
+                {
+                    callback_stopped.store(true, Ordering::SeqCst);
+                    callback_run_loop.stop();
+                }
+            })
+            .and_then(|observer| {
+                for notification in &notifications {
+                    observer.add_notification(&element, CFString::new(notification))?;
+                }
+                Ok(observer)
+            });
+
+            let observer = match observer {
+                Ok(observer) => observer,
+                Err(e) => {
+                    let _ = setup_sender.send(Err(e));
+                    return;
+                }
+            };
+
+            unsafe {
+                run_loop.add_source(&observer.run_loop_source(), kCFRunLoopDefaultMode);
+            }
+            let _ = setup_sender.send(Ok(run_loop));
+
+            // A stop request can land before we enter the run loop, so don't rely on it alone.
+            while !thread_stopped.load(Ordering::SeqCst) {
+                unsafe {
+                    CFRunLoop::run_in_mode(kCFRunLoopDefaultMode, Duration::from_secs(1), false);
+                }
+            }
+        });
+
+        match setup_receiver.recv() {
+            Ok(Ok(run_loop)) => Ok(Self {
+                #[cfg(feature = "test-support")]
+                sender: None,
+                receiver,
+                run_loop: Some(run_loop),
+                stopped,
+                thread: Some(thread),
+            }),
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                Err(e)
+            }
+            Err(_) => match thread.join() {
+                Err(payload) => panic::resume_unwind(payload),
+                Ok(()) => unreachable!("observer thread exited without reporting"),
+            },
+        }
+    }
+}
+
+// Lets code built on notification streams be exercised without a live app or accessibility
+// permissions.
+#[cfg(feature = "test-support")]
+impl NotificationStream {
+    /// A stream with no observer behind it; it only yields what's passed to `post`.
+    pub fn detached() -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            sender: Some(sender),
+            receiver,
+            run_loop: None,
+            stopped: Arc::new(AtomicBool::new(false)),
+            thread: None,
+        }
+    }
+
+    /// Queues a synthetic notification as if the observer had delivered it. Only detached
+    /// streams accept these; on a live stream this does nothing.
+    pub fn post(&self, element: &AXUIElement, notification: &str) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(AXNotification {
+                element: element.clone(),
+                notification: CFString::new(notification),
+            });
+        }
+    }
+
+    /// Drops the detached stream's sender, so that receiving reports the stream as closed once
+    /// everything already posted has been read.
+    pub fn close(&mut self) {
+        self.sender = None;
+    }
+}
+
+impl Deref for NotificationStream {
+    type Target = Receiver<AXNotification>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.receiver
+    }
+}
+
+impl Drop for NotificationStream {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        if let Some(run_loop) = &self.run_loop {
+            run_loop.stop();
+        }
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum WindowEvent {
+    Created(AXUIElement),
+    Destroyed(AXUIElement),
+    Moved(AXUIElement),
+    Resized(AXUIElement),
+}
+
+pub struct WindowObserver {
+    stream: NotificationStream,
+    windows: RefCell<HashSet<AXUIElement>>,
+}
+
+impl WindowObserver {
+    pub fn new(pid: pid_t) -> Result<Self, Error> {
+        let app = AXUIElement::application(pid);
+        let stream = NotificationStream::new(
+            pid,
+            &app,
+            &[
+                Notification::WindowCreated,
+                Notification::UIElementDestroyed,
+                Notification::WindowMoved,
+                Notification::WindowResized,
+            ],
+        )?;
+
+        Ok(Self::with_stream(&app, stream))
+    }
+
+    /// Wraps an existing stream, e.g. a detached one with synthetic notifications already
+    /// posted. `app`'s current windows are the ones whose destruction gets reported.
+    #[cfg(feature = "test-support")]
+    pub fn from_stream(app: &AXUIElement, stream: NotificationStream) -> Self {
+        Self::with_stream(app, stream)
+    }
+
+    fn with_stream(app: &AXUIElement, stream: NotificationStream) -> Self {
+        // AXUIElementDestroyed fires for every kind of element, so keep track of which ones are
+        // windows.
+        let windows = app
+            .windows()
+            .map(|windows| windows.iter().map(|w| w.clone()).collect())
+            .unwrap_or_default();
+
+        Self {
+            stream,
+            windows: RefCell::new(windows),
+        }
+    }
+
+    /// Blocks until the next window event. Returns `None` once the observer thread is gone.
+    pub fn recv(&self) -> Option<WindowEvent> {
+        recv_filtered(&self.stream, None, |n| self.translate(n))
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WindowEvent> {
+        recv_filtered(&self.stream, Some(timeout), |n| self.translate(n))
+    }
+
+    /// Stops observing and waits for the observer thread to exit.
+    pub fn shutdown(self) {
+        drop(self.stream);
+    }
+
+    #[allow(non_upper_case_globals)]
+    fn translate(&self, notification: AXNotification) -> Option<WindowEvent> {
+        let AXNotification {
+            element,
+            notification,
+        } = notification;
+        let mut windows = self.windows.borrow_mut();
+
+        match &*notification.to_string() {
+            kAXWindowCreatedNotification => {
+                windows.insert(element.clone());
+                Some(WindowEvent::Created(element))
+            }
+            kAXUIElementDestroyedNotification => windows
+                .remove(&element)
+                .then(|| WindowEvent::Destroyed(element)),
+            kAXWindowMovedNotification => Some(WindowEvent::Moved(element)),
+            kAXWindowResizedNotification => Some(WindowEvent::Resized(element)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum FocusEvent {
+    /// The focused element changed; `None` if nothing in the app has focus now.
+    Element(Option<AXUIElement>),
+    /// The focused window changed; `None` if the app has no focused window now.
+    Window(Option<AXUIElement>),
+    /// Focus changed, but the app couldn't say what has focus now.
+    Error(Error),
+}
+
+pub struct FocusObserver {
+    app: AXUIElement,
+    stream: NotificationStream,
+}
+
+impl FocusObserver {
+    pub fn new(pid: pid_t) -> Result<Self, Error> {
+        let app = AXUIElement::application(pid);
+        let stream = NotificationStream::new(
+            pid,
+            &app,
+            &[
+                Notification::FocusedUIElementChanged,
+                Notification::FocusedWindowChanged,
+            ],
+        )?;
+
+        Ok(Self { app, stream })
+    }
+
+    /// Wraps an existing stream, e.g. a detached one with synthetic notifications already
+    /// posted. Focus is looked up on `app` when a notification arrives.
+    #[cfg(feature = "test-support")]
+    pub fn from_stream(app: AXUIElement, stream: NotificationStream) -> Self {
+        Self { app, stream }
+    }
+
+    /// Blocks until focus moves. Returns `None` once the observer thread is gone.
+    pub fn recv(&self) -> Option<FocusEvent> {
+        recv_filtered(&self.stream, None, |n| self.translate(n))
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<FocusEvent> {
+        recv_filtered(&self.stream, Some(timeout), |n| self.translate(n))
+    }
+
+    /// Stops observing and waits for the observer thread to exit.
+    pub fn shutdown(self) {
+        drop(self.stream);
+    }
+
+    #[allow(non_upper_case_globals)]
+    fn translate(&self, notification: AXNotification) -> Option<FocusEvent> {
+        // The notification's element isn't always the newly focused one (e.g. when focus moves
+        // to nothing), so ask the app instead.
+        match &*notification.notification.to_string() {
+            kAXFocusedUIElementChangedNotification => Some(
+                focused(self.app.focused_ui_element())
+                    .map_or_else(FocusEvent::Error, FocusEvent::Element),
+            ),
+            kAXFocusedWindowChangedNotification => Some(
+                focused(self.app.focused_window())
+                    .map_or_else(FocusEvent::Error, FocusEvent::Window),
+            ),
+            _ => None,
+        }
+    }
+}
+
+// Only a missing value means nothing has focus; other errors say nothing about where focus went.
+fn focused(result: Result<AXUIElement, Error>) -> Result<Option<AXUIElement>, Error> {
+    match result {
+        Ok(element) => Ok(Some(element)),
+        Err(Error::Ax(AXErrorKind::NoValue | AXErrorKind::AttributeUnsupported)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ValueEvent {
+    pub element: AXUIElement,
+    /// The element's value when the event was delivered, which may be newer than the change
+    /// that triggered it.
+    pub value: Option<CFType>,
+}
+
+// When an element's last event went out, and whether another change arrived since.
+struct Throttle {
+    delivered: Instant,
+    pending: bool,
+}
+
+/// Watches for `AXValueChanged` on an element and its descendants.
+///
+/// Elements like progress indicators can post value changes far faster than anyone wants to
+/// handle them. With `with_throttle`, each element produces at most one event per interval; any
+/// changes in between are coalesced into a single event at the end of the interval.
+pub struct ValueObserver {
+    stream: NotificationStream,
+    interval: Duration,
+    throttles: RefCell<HashMap<AXUIElement, Throttle>>,
+}
+
+impl ValueObserver {
+    pub fn new(pid: pid_t, element: &AXUIElement) -> Result<Self, Error> {
+        let stream = NotificationStream::new(pid, element, &[Notification::ValueChanged])?;
+
+        Ok(Self::with_stream(stream))
+    }
+
+    /// Wraps an existing stream, e.g. a detached one with synthetic notifications already
+    /// posted.
+    #[cfg(feature = "test-support")]
+    pub fn from_stream(stream: NotificationStream) -> Self {
+        Self::with_stream(stream)
+    }
+
+    fn with_stream(stream: NotificationStream) -> Self {
+        Self {
+            stream,
+            interval: Duration::ZERO,
+            throttles: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_throttle(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Blocks until the next value change. Returns `None` once the observer thread is gone.
+    pub fn recv(&self) -> Option<ValueEvent> {
+        self.next(None)
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ValueEvent> {
+        self.next(Some(Instant::now() + timeout))
+    }
+
+    /// Stops observing and waits for the observer thread to exit.
+    pub fn shutdown(self) {
+        drop(self.stream);
+    }
+
+    fn next(&self, deadline: Option<Instant>) -> Option<ValueEvent> {
+        loop {
+            let now = Instant::now();
+            if let Some(element) = self.take_due(now) {
+                return Some(self.deliver(element));
+            }
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                return None;
+            }
+
+            // Wake up for whichever comes first: the caller's deadline or the end of a pending
+            // element's interval.
+            let wake = self
+                .throttles
+                .borrow()
+                .values()
+                .filter(|throttle| throttle.pending)
+                .map(|throttle| throttle.delivered + self.interval)
+                .chain(deadline)
+                .min();
+            let notification = match wake {
+                Some(wake) => match self
+                    .stream
+                    .recv_timeout(wake.saturating_duration_since(now))
+                {
+                    Ok(notification) => notification,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => return None,
+                },
+                None => self.stream.recv().ok()?,
+            };
+
+            if notification.notification != kAXValueChangedNotification {
+                continue;
+            }
+
+            let element = notification.element;
+            match self.throttles.borrow_mut().get_mut(&element) {
+                Some(throttle) if throttle.delivered.elapsed() < self.interval => {
+                    throttle.pending = true;
+                    continue;
+                }
+                _ => {}
+            }
+            return Some(self.deliver(element));
+        }
+    }
+
+    // Finds a pending element whose interval is over, forgetting any idle ones along the way.
+    fn take_due(&self, now: Instant) -> Option<AXUIElement> {
+        let mut throttles = self.throttles.borrow_mut();
+        throttles.retain(|_, throttle| {
+            throttle.pending || now.duration_since(throttle.delivered) < self.interval
+        });
+
+        throttles
+            .iter()
+            .find(|(_, throttle)| {
+                throttle.pending && now.duration_since(throttle.delivered) >= self.interval
+            })
+            .map(|(element, _)| element.clone())
+    }
+
+    fn deliver(&self, element: AXUIElement) -> ValueEvent {
+        // Read the value now rather than when the notification arrived, so a coalesced event
+        // carries the latest one.
+        let value = element.value().ok();
+
+        if !self.interval.is_zero() {
+            self.throttles.borrow_mut().insert(
+                element.clone(),
+                Throttle {
+                    delivered: Instant::now(),
+                    pending: false,
+                },
+            );
+        }
+
+        ValueEvent { element, value }
+    }
+}
+
+fn recv_filtered<T>(
+    stream: &NotificationStream,
+    timeout: Option<Duration>,
+    mut translate: impl FnMut(AXNotification) -> Option<T>,
+) -> Option<T> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let notification = match deadline {
+            Some(deadline) => stream
+                .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                .ok()?,
+            None => stream.recv().ok()?,
+        };
+
+        if let Some(event) = translate(notification) {
+            return Some(event);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-support"))]
+mod tests {
+    use super::*;
+    use crate::util::test_element as element;
+
+    fn name(notification: &AXNotification) -> String {
+        notification.notification.to_string()
+    }
+
+    #[test]
+    fn focus_moving_to_nothing_is_not_an_error() {
+        for kind in [AXErrorKind::NoValue, AXErrorKind::AttributeUnsupported].iter() {
+            assert!(matches!(focused(Err(Error::Ax(*kind))), Ok(None)));
+        }
+    }
+
+    #[test]
+    fn focus_read_failures_are_reported() {
+        for kind in [AXErrorKind::CannotComplete, AXErrorKind::APIDisabled].iter() {
+            assert!(matches!(
+                focused(Err(Error::Ax(*kind))),
+                Err(Error::Ax(k)) if k == *kind
+            ));
+        }
+    }
+
+    #[test]
+    fn recv_filtered_skips_untranslated_notifications() {
+        let stream = NotificationStream::detached();
+        stream.post(&element(1), kAXWindowMovedNotification);
+        stream.post(&element(2), kAXWindowCreatedNotification);
+
+        let created = recv_filtered(&stream, None, |n| {
+            (name(&n) == kAXWindowCreatedNotification).then_some(n.element)
+        });
+        assert_eq!(created, Some(element(2)));
+    }
+
+    #[test]
+    fn recv_filtered_times_out() {
+        let stream = NotificationStream::detached();
+        stream.post(&element(1), kAXWindowMovedNotification);
+
+        let event = recv_filtered(&stream, Some(Duration::from_millis(10)), |_| None::<()>);
+        assert!(event.is_none());
+    }
+
+    #[test]
+    fn recv_filtered_ends_when_stream_closes() {
+        let mut stream = NotificationStream::detached();
+        stream.post(&element(1), kAXWindowCreatedNotification);
+        stream.close();
+
+        assert!(recv_filtered(&stream, None, |n| Some(n.element)).is_some());
+        assert!(recv_filtered(&stream, None, |n| Some(n.element)).is_none());
+    }
+
+    #[test]
+    fn window_observer_only_reports_destroyed_windows() {
+        let stream = NotificationStream::detached();
+        stream.post(&element(1), kAXWindowCreatedNotification);
+        stream.post(&element(2), kAXUIElementDestroyedNotification);
+        stream.post(&element(1), kAXUIElementDestroyedNotification);
+        // An app that isn't running has no windows to start with.
+        let observer = WindowObserver::from_stream(&element(i32::MAX), stream);
+
+        let timeout = Duration::from_millis(10);
+        assert!(
+            matches!(observer.recv_timeout(timeout), Some(WindowEvent::Created(w)) if w == element(1))
+        );
+        assert!(
+            matches!(observer.recv_timeout(timeout), Some(WindowEvent::Destroyed(w)) if w == element(1))
+        );
+        assert!(observer.recv_timeout(timeout).is_none());
+    }
+
+    #[test]
+    fn value_observer_coalesces_changes_within_interval() {
+        let stream = NotificationStream::detached();
+        for _ in 0..3 {
+            stream.post(&element(1), kAXValueChangedNotification);
+        }
+        stream.post(&element(2), kAXValueChangedNotification);
+        let interval = Duration::from_millis(50);
+        let observer = ValueObserver::from_stream(stream).with_throttle(interval);
+
+        let timeout = Duration::from_millis(200);
+        let started = Instant::now();
+        assert_eq!(
+            observer.recv_timeout(timeout).map(|e| e.element),
+            Some(element(1))
+        );
+        assert_eq!(
+            observer.recv_timeout(timeout).map(|e| e.element),
+            Some(element(2))
+        );
+        // The two later changes to the first element arrive as one event, after the interval.
+        assert_eq!(
+            observer.recv_timeout(timeout).map(|e| e.element),
+            Some(element(1))
+        );
+        assert!(started.elapsed() >= interval);
+        assert!(observer.recv_timeout(Duration::from_millis(100)).is_none());
+    }
+}
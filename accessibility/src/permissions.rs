@@ -0,0 +1,27 @@
+use accessibility_sys::{
+    kAXTrustedCheckOptionPrompt, AXAPIEnabled, AXIsProcessTrusted, AXIsProcessTrustedWithOptions,
+};
+use core_foundation::{
+    base::TCFType, boolean::CFBoolean, dictionary::CFDictionary, string::CFString,
+};
+
+/// Whether accessibility is enabled system-wide. This is separate from whether this particular
+/// process is trusted; see `is_process_trusted`.
+///
+/// Wraps the deprecated `AXAPIEnabled`, so treat a `true` result as advisory.
+pub fn api_enabled() -> bool {
+    unsafe { AXAPIEnabled() }
+}
+
+pub fn is_process_trusted() -> bool {
+    unsafe { AXIsProcessTrusted() }
+}
+
+pub fn is_process_trusted_with_prompt(prompt: bool) -> bool {
+    let options = CFDictionary::from_CFType_pairs(&[(
+        unsafe { CFString::wrap_under_get_rule(kAXTrustedCheckOptionPrompt) },
+        CFBoolean::from(prompt),
+    )]);
+
+    unsafe { AXIsProcessTrustedWithOptions(options.as_concrete_TypeRef()) }
+}
@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    value::{AXErrorValue, AXValueData},
+    AXUIElement, AXUIElementAttributes,
+};
+use accessibility_sys::{kAXChildrenAttribute, AXError};
+use core_foundation::{
+    array::CFArray,
+    base::{CFHash, CFType, TCFType},
+    boolean::CFBoolean,
+    number::{CFNumber, CFNumberIsFloatType},
+    string::CFString,
+};
+
+// Untagged deserialization picks the first variant that fits and ignores extra fields, so a
+// variant has to come before any whose fields are a subset of its own (Rect before Point, and
+// Element, whose fields are all optional, last among the maps).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum SnapshotValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    Point {
+        x: f64,
+        y: f64,
+    },
+    Size {
+        width: f64,
+        height: f64,
+    },
+    Range {
+        location: i64,
+        length: i64,
+    },
+    Error {
+        code: AXError,
+    },
+    Unknown {
+        description: String,
+    },
+    /// A reference to another element. Role and title are for reading; `pid` and `hash` (the
+    /// element's CFHash) tell apart elements that look the same, such as two "OK" buttons.
+    Element {
+        role: Option<String>,
+        title: Option<String>,
+        pid: Option<i32>,
+        hash: Option<u64>,
+    },
+    Array(Vec<SnapshotValue>),
+}
+
+impl SnapshotValue {
+    pub fn from_cf(value: &CFType) -> Self {
+        if let Some(string) = value.downcast::<CFString>() {
+            Self::String(string.to_string())
+        } else if let Some(boolean) = value.downcast::<CFBoolean>() {
+            Self::Bool(boolean.into())
+        } else if let Some(number) = value.downcast::<CFNumber>() {
+            Self::from_number(&number)
+        } else if let Some(element) = value.downcast::<AXUIElement>() {
+            Self::Element {
+                role: element.role().ok().map(|r| r.to_string()),
+                title: element.title().ok().map(|t| t.to_string()),
+                pid: element.pid().ok(),
+                hash: Some(unsafe { CFHash(element.as_CFTypeRef()) } as u64),
+            }
+        } else if value.instance_of::<CFArray>() {
+            let array =
+                unsafe { CFArray::<CFType>::wrap_under_get_rule(value.as_CFTypeRef() as _) };
+            Self::Array(array.iter().map(|item| Self::from_cf(&item)).collect())
+        } else if let Some(data) = AXValueData::from_cf(value) {
+            Self::from_ax_value(data).unwrap_or_else(|| Self::unknown(value))
+        } else {
+            Self::unknown(value)
+        }
+    }
+
+    fn from_number(number: &CFNumber) -> Self {
+        if unsafe { CFNumberIsFloatType(number.as_concrete_TypeRef()) } != 0 {
+            number.to_f64().map(Self::Float)
+        } else {
+            number.to_i64().map(Self::Integer)
+        }
+        .unwrap_or_else(|| Self::unknown(&number.as_CFType()))
+    }
+
+    fn from_ax_value(data: AXValueData) -> Option<Self> {
+        match data {
+            AXValueData::Point(point) => Some(Self::Point {
+                x: point.x,
+                y: point.y,
+            }),
+            AXValueData::Size(size) => Some(Self::Size {
+                width: size.width,
+                height: size.height,
+            }),
+            AXValueData::Rect(rect) => Some(Self::Rect {
+                x: rect.origin.x,
+                y: rect.origin.y,
+                width: rect.size.width,
+                height: rect.size.height,
+            }),
+            AXValueData::Range(range) => Some(Self::Range {
+                location: range.location as i64,
+                length: range.length as i64,
+            }),
+            AXValueData::Error(AXErrorValue(code)) => Some(Self::Error { code }),
+            AXValueData::Unknown(_) => None,
+        }
+    }
+
+    fn unknown(value: &CFType) -> Self {
+        Self::Unknown {
+            description: format!("{:?}", value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Snapshot {
+    pub attributes: BTreeMap<String, SnapshotValue>,
+    pub children: Vec<Snapshot>,
+}
+
+impl Snapshot {
+    pub fn capture(element: &AXUIElement, max_depth: usize) -> Self {
+        let attributes = element
+            .attributes_map()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(name, _)| name != kAXChildrenAttribute)
+            .map(|(name, value)| (name, SnapshotValue::from_cf(&value)))
+            .collect();
+
+        let children = if max_depth > 0 {
+            element
+                .children()
+                .map(|children| {
+                    children
+                        .iter()
+                        .map(|child| Self::capture(&child, max_depth - 1))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            attributes,
+            children,
+        }
+    }
+}
+
+/// A captured subtree stored as a flat list of nodes, so it can be written to disk and inspected
+/// later without the app running. The root is `nodes[0]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementTree {
+    pub nodes: Vec<ElementNode>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElementNode {
+    pub attributes: BTreeMap<String, SnapshotValue>,
+    /// Indices into `ElementTree::nodes`.
+    pub children: Vec<usize>,
+}
+
+impl ElementTree {
+    pub fn capture(root: &AXUIElement, max_depth: usize) -> Self {
+        Snapshot::capture(root, max_depth).into()
+    }
+
+    pub fn root(&self) -> Option<&ElementNode> {
+        self.nodes.first()
+    }
+
+    pub fn children<'a>(&'a self, node: &'a ElementNode) -> impl Iterator<Item = &'a ElementNode> {
+        node.children.iter().filter_map(move |&i| self.nodes.get(i))
+    }
+}
+
+impl From<Snapshot> for ElementTree {
+    fn from(snapshot: Snapshot) -> Self {
+        fn push(nodes: &mut Vec<ElementNode>, snapshot: Snapshot) -> usize {
+            let index = nodes.len();
+            nodes.push(ElementNode {
+                attributes: snapshot.attributes,
+                children: Vec::new(),
+            });
+            let children = snapshot
+                .children
+                .into_iter()
+                .map(|child| push(nodes, child))
+                .collect();
+            nodes[index].children = children;
+            index
+        }
+
+        let mut nodes = Vec::new();
+        push(&mut nodes, snapshot);
+        Self { nodes }
+    }
+}
+
+/// One attribute that differs between two snapshots. `old` is `None` when the attribute was
+/// added and `new` is `None` when it was removed.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AttributeChange {
+    /// Child indices leading from the root to the element that changed.
+    pub path: Vec<usize>,
+    pub name: String,
+    pub old: Option<SnapshotValue>,
+    pub new: Option<SnapshotValue>,
+}
+
+pub fn diff(old: &Snapshot, new: &Snapshot) -> Vec<AttributeChange> {
+    let mut changes = Vec::new();
+    diff_into(Some(old), Some(new), &mut Vec::new(), &mut changes);
+    changes
+}
+
+fn diff_into(
+    old: Option<&Snapshot>,
+    new: Option<&Snapshot>,
+    path: &mut Vec<usize>,
+    changes: &mut Vec<AttributeChange>,
+) {
+    let empty = BTreeMap::new();
+    let old_attributes = old.map_or(&empty, |s| &s.attributes);
+    let new_attributes = new.map_or(&empty, |s| &s.attributes);
+    let names: BTreeSet<&String> = old_attributes.keys().chain(new_attributes.keys()).collect();
+
+    for name in names {
+        let (old_value, new_value) = (old_attributes.get(name), new_attributes.get(name));
+
+        if old_value != new_value {
+            changes.push(AttributeChange {
+                path: path.clone(),
+                name: name.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            });
+        }
+    }
+
+    let old_children = old.map_or(&[][..], |s| &s.children);
+    let new_children = new.map_or(&[][..], |s| &s.children);
+
+    for i in 0..old_children.len().max(new_children.len()) {
+        path.push(i);
+        diff_into(old_children.get(i), new_children.get(i), path, changes);
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(attributes: &[(&str, SnapshotValue)], children: Vec<Snapshot>) -> Snapshot {
+        Snapshot {
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+            children,
+        }
+    }
+
+    fn string(s: &str) -> SnapshotValue {
+        SnapshotValue::String(s.to_owned())
+    }
+
+    #[test]
+    fn diff_of_identical_snapshots_is_empty() {
+        let old = snapshot(
+            &[
+                ("AXRole", string("AXWindow")),
+                ("AXTitle", string("Untitled")),
+            ],
+            vec![snapshot(
+                &[("AXEnabled", SnapshotValue::Bool(true))],
+                vec![],
+            )],
+        );
+
+        assert_eq!(diff(&old, &old.clone()), vec![]);
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_attributes() {
+        let old = snapshot(
+            &[
+                ("AXRole", string("AXWindow")),
+                ("AXTitle", string("Untitled")),
+                ("AXMinimized", SnapshotValue::Bool(false)),
+            ],
+            vec![],
+        );
+        let new = snapshot(
+            &[
+                ("AXRole", string("AXWindow")),
+                ("AXTitle", string("Notes.txt")),
+                ("AXDocument", string("file:///Notes.txt")),
+            ],
+            vec![],
+        );
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                AttributeChange {
+                    path: vec![],
+                    name: "AXDocument".to_owned(),
+                    old: None,
+                    new: Some(string("file:///Notes.txt")),
+                },
+                AttributeChange {
+                    path: vec![],
+                    name: "AXMinimized".to_owned(),
+                    old: Some(SnapshotValue::Bool(false)),
+                    new: None,
+                },
+                AttributeChange {
+                    path: vec![],
+                    name: "AXTitle".to_owned(),
+                    old: Some(string("Untitled")),
+                    new: Some(string("Notes.txt")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_tells_apart_elements_that_look_alike() {
+        let ok_button = |hash| SnapshotValue::Element {
+            role: Some("AXButton".to_owned()),
+            title: Some("OK".to_owned()),
+            pid: Some(42),
+            hash: Some(hash),
+        };
+        let old = snapshot(&[("AXFocusedUIElement", ok_button(1))], vec![]);
+        let new = snapshot(&[("AXFocusedUIElement", ok_button(2))], vec![]);
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![AttributeChange {
+                path: vec![],
+                name: "AXFocusedUIElement".to_owned(),
+                old: Some(ok_button(1)),
+                new: Some(ok_button(2)),
+            }]
+        );
+        assert_eq!(diff(&old, &old.clone()), vec![]);
+    }
+
+    #[test]
+    fn diff_follows_children_by_index() {
+        let old = snapshot(
+            &[],
+            vec![
+                snapshot(&[("AXValue", SnapshotValue::Integer(1))], vec![]),
+                snapshot(&[("AXRole", string("AXButton"))], vec![]),
+            ],
+        );
+        let new = snapshot(
+            &[],
+            vec![
+                snapshot(&[("AXValue", SnapshotValue::Integer(2))], vec![]),
+                snapshot(&[("AXRole", string("AXButton"))], vec![]),
+                snapshot(&[("AXRole", string("AXCheckBox"))], vec![]),
+            ],
+        );
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                AttributeChange {
+                    path: vec![0],
+                    name: "AXValue".to_owned(),
+                    old: Some(SnapshotValue::Integer(1)),
+                    new: Some(SnapshotValue::Integer(2)),
+                },
+                AttributeChange {
+                    path: vec![2],
+                    name: "AXRole".to_owned(),
+                    old: None,
+                    new: Some(string("AXCheckBox")),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::slice;
+
+use accessibility_sys::{
+    AXTextMarkerCreate, AXTextMarkerGetBytePtr, AXTextMarkerGetLength, AXTextMarkerGetTypeID,
+    AXTextMarkerRangeCopyEndMarker, AXTextMarkerRangeCopyStartMarker, AXTextMarkerRangeCreate,
+    AXTextMarkerRangeGetTypeID, AXTextMarkerRangeRef, AXTextMarkerRef,
+};
+use core_foundation::{
+    base::{kCFAllocatorDefault, CFIndex, TCFType},
+    declare_TCFType, impl_CFTypeDescription, impl_TCFType,
+};
+
+// An opaque position in an app's text. Only the app that produced a marker can interpret it, so
+// all we can do is compare markers and hand them back.
+declare_TCFType!(AXTextMarker, AXTextMarkerRef);
+impl_TCFType!(AXTextMarker, AXTextMarkerRef, AXTextMarkerGetTypeID);
+impl_CFTypeDescription!(AXTextMarker);
+
+impl AXTextMarker {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        unsafe {
+            Self::wrap_under_create_rule(AXTextMarkerCreate(
+                kCFAllocatorDefault,
+                bytes.as_ptr(),
+                bytes.len() as CFIndex,
+            ))
+        }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(
+                AXTextMarkerGetBytePtr(self.0),
+                AXTextMarkerGetLength(self.0) as usize,
+            )
+        }
+    }
+}
+
+declare_TCFType!(AXTextMarkerRange, AXTextMarkerRangeRef);
+impl_TCFType!(
+    AXTextMarkerRange,
+    AXTextMarkerRangeRef,
+    AXTextMarkerRangeGetTypeID
+);
+impl_CFTypeDescription!(AXTextMarkerRange);
+
+impl AXTextMarkerRange {
+    pub fn new(start: &AXTextMarker, end: &AXTextMarker) -> Self {
+        unsafe {
+            Self::wrap_under_create_rule(AXTextMarkerRangeCreate(
+                kCFAllocatorDefault,
+                start.0,
+                end.0,
+            ))
+        }
+    }
+
+    pub fn start(&self) -> AXTextMarker {
+        unsafe { AXTextMarker::wrap_under_create_rule(AXTextMarkerRangeCopyStartMarker(self.0)) }
+    }
+
+    pub fn end(&self) -> AXTextMarker {
+        unsafe { AXTextMarker::wrap_under_create_rule(AXTextMarkerRangeCopyEndMarker(self.0)) }
+    }
+}
@@ -1,40 +1,68 @@
 use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
     ffi::c_uchar,
-    fmt::Debug,
-    thread,
-    time::{Duration, Instant},
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    time::Duration,
 };
 
+#[cfg(feature = "private-api")]
+use accessibility_sys::{_AXUIElementCreateWithRemoteToken, _AXUIElementRemoteTokenCreate};
 use accessibility_sys::{
-    pid_t, AXUIElementCopyActionNames, AXUIElementCopyAttributeNames,
-    AXUIElementCopyAttributeValue, AXUIElementCreateApplication, AXUIElementCreateSystemWide,
-    AXUIElementGetTypeID, AXUIElementIsAttributeSettable, AXUIElementPerformAction, AXUIElementRef,
-    AXUIElementSetAttributeValue, AXUIElementSetMessagingTimeout,
+    kAXCopyMultipleAttributeOptionStopOnError, kAXWindowRole, pid_t,
+    AXUIElementCopyActionDescription, AXUIElementCopyActionNames, AXUIElementCopyAttributeNames,
+    AXUIElementCopyAttributeValue, AXUIElementCopyAttributeValues,
+    AXUIElementCopyElementAtPosition, AXUIElementCopyMultipleAttributeValues,
+    AXUIElementCopyParameterizedAttributeNames, AXUIElementCopyParameterizedAttributeValue,
+    AXUIElementCreateApplication, AXUIElementCreateSystemWide, AXUIElementGetAttributeValueCount,
+    AXUIElementGetPid, AXUIElementGetTypeID, AXUIElementIsAttributeSettable,
+    AXUIElementPerformAction, AXUIElementRef, AXUIElementSetAttributeValue,
+    AXUIElementSetMessagingTimeout,
 };
 use cocoa::{
     base::{id, nil},
     foundation::{NSAutoreleasePool, NSFastEnumeration, NSString},
 };
+#[cfg(feature = "private-api")]
+use core_foundation::data::CFData;
 use core_foundation::{
     array::CFArray,
-    base::{CFType, TCFType, TCFTypeRef},
+    base::{CFHash, CFIndex, CFRange, CFType, TCFType},
+    boolean::CFBoolean,
     declare_TCFType, impl_TCFType,
+    number::CFNumber,
     string::CFString,
 };
+use core_graphics::{
+    event::CGEvent,
+    event_source::{CGEventSource, CGEventSourceStateID},
+};
+use core_graphics_types::geometry::{CGPoint, CGRect};
 use objc::{class, msg_send, rc::autoreleasepool, sel, sel_impl};
 
 use crate::{
-    util::{ax_call, ax_call_void},
-    AXAttribute, AXUIElementAttributes, Error,
+    geometry,
+    util::{ax_call, ax_call_void, cast_attribute, ns_string_to_string, poll_until},
+    value::{AXErrorValue, AXValue, FromCFType, NumberKind},
+    AXAttribute, AXErrorKind, AXParameterizedAttribute, AXUIElementActions, AXUIElementAttributes,
+    Ancestors, Descendants, Error,
 };
 
 declare_TCFType!(AXUIElement, AXUIElementRef);
 impl_TCFType!(AXUIElement, AXUIElementRef, AXUIElementGetTypeID);
 
+// PartialEq and Eq come from impl_TCFType via CFEqual; hash consistently with that.
+impl Hash for AXUIElement {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { CFHash(self.as_CFTypeRef()) }.hash(state);
+    }
+}
+
 impl Debug for AXUIElement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
-            return self.debug_all(f);
+            return self.debug_shallow().fmt(f);
         }
         let (role, title) = (self.role(), self.title());
         let mut fmt = f.debug_tuple(
@@ -49,6 +77,128 @@ impl Debug for AXUIElement {
     }
 }
 
+/// Debug output for an element that expands element-valued attributes (parent, window, children
+/// and so on) up to `depth` levels deep. Anything deeper is printed as a short summary.
+pub struct DebugElement<'a> {
+    element: &'a AXUIElement,
+    depth: usize,
+    visited: Option<&'a RefCell<HashSet<AXUIElement>>>,
+}
+
+impl Debug for DebugElement<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.visited {
+            Some(visited) => self.element.debug_all(f, self.depth, visited),
+            None => self
+                .element
+                .debug_all(f, self.depth, &RefCell::new(HashSet::new())),
+        }
+    }
+}
+
+/// Debug output that reads only the listed attributes, in the order given. See
+/// `AXUIElement::debug_with`.
+pub struct DebugAttributes<'a> {
+    element: &'a AXUIElement,
+    attributes: &'a [AXAttribute<CFType>],
+}
+
+impl Debug for DebugAttributes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut fmt = f.debug_struct("AXUIElement");
+        for attribute in self.attributes {
+            if let Ok(value) = self.element.attribute(attribute) {
+                fmt.field(&attribute.as_CFString().to_string(), &value);
+            }
+        }
+        fmt.finish()
+    }
+}
+
+// One line, e.g. `Button "OK" [subrole=AXCloseButton]`.
+impl Display for AXUIElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let role = self.role().map(|r| r.to_string()).unwrap_or_default();
+        match role.strip_prefix("AX").unwrap_or(&role) {
+            "" => write!(f, "AXUIElement")?,
+            role => write!(f, "{}", role)?,
+        }
+
+        let title = self.title().map(|t| t.to_string()).unwrap_or_default();
+        if !title.is_empty() {
+            write!(f, " {:?}", title)?;
+        }
+
+        let mut details = Vec::new();
+        if let Ok(subrole) = self.subrole() {
+            details.push(format!("subrole={}", subrole));
+        }
+        if let Ok(identifier) = self.identifier() {
+            details.push(format!("identifier={}", identifier));
+        }
+        if !details.is_empty() {
+            write!(f, " [{}]", details.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An element that can be handed to another thread.
+///
+/// An `AXUIElementRef` is an immutable reference to a remote UI object: a pid plus an opaque
+/// token. Its retain count is atomic and the AX calls made through it are plain IPC, so they
+/// don't depend on the run loop of the thread that created it. Only observers are tied to a run
+/// loop, and those aren't covered by this wrapper.
+pub struct SendableElement(AXUIElement);
+
+unsafe impl Send for SendableElement {}
+
+impl SendableElement {
+    pub fn new(element: AXUIElement) -> Self {
+        Self(element)
+    }
+
+    pub fn into_inner(self) -> AXUIElement {
+        self.0
+    }
+}
+
+impl From<AXUIElement> for SendableElement {
+    fn from(element: AXUIElement) -> Self {
+        Self(element)
+    }
+}
+
+#[cfg(feature = "tokio")]
+struct SendableValue(CFType);
+
+#[cfg(feature = "tokio")]
+unsafe impl Send for SendableValue {}
+
+/// Whatever element has keyboard focus, in whichever app is active.
+pub fn focused_element() -> Result<AXUIElement, Error> {
+    AXUIElement::system_wide().focused_ui_element()
+}
+
+pub fn running_applications() -> Vec<(pid_t, String)> {
+    unsafe {
+        autoreleasepool(|| {
+            let workspace: id = msg_send![class![NSWorkspace], sharedWorkspace];
+            let apps: id = msg_send![workspace, runningApplications];
+
+            apps.iter()
+                .map(|app| {
+                    let pid: pid_t = msg_send![app, processIdentifier];
+                    let name: id = msg_send![app, localizedName];
+
+                    (pid, ns_string_to_string(name))
+                })
+                .collect()
+        })
+    }
+}
+
 impl AXUIElement {
     pub fn system_wide() -> Self {
         unsafe { Self::wrap_under_create_rule(AXUIElementCreateSystemWide()) }
@@ -58,6 +208,23 @@ impl AXUIElement {
         unsafe { Self::wrap_under_create_rule(AXUIElementCreateApplication(pid)) }
     }
 
+    pub fn application_with_pid(pid: pid_t) -> Result<Self, Error> {
+        unsafe {
+            autoreleasepool(|| {
+                let app: id = msg_send![
+                    class![NSRunningApplication],
+                    runningApplicationWithProcessIdentifier: pid
+                ];
+
+                if app != nil {
+                    Ok(Self::application(pid))
+                } else {
+                    Err(Error::NotFound)
+                }
+            })
+        }
+    }
+
     pub fn application_with_bundle(bundle_id: &str) -> Result<Self, Error> {
         unsafe {
             autoreleasepool(|| {
@@ -84,23 +251,219 @@ impl AXUIElement {
         bundle_id: &str,
         timeout: Duration,
     ) -> Result<Self, Error> {
-        let deadline = Instant::now() + timeout;
+        let mut last_error = Error::NotFound;
 
-        loop {
-            match Self::application_with_bundle(bundle_id) {
-                Ok(result) => return Ok(result),
+        poll_until(
+            timeout,
+            Duration::from_millis(250),
+            || match Self::application_with_bundle(bundle_id) {
+                Ok(result) => Some(Ok(result)),
                 Err(e) => {
-                    let now = Instant::now();
-
-                    if now >= deadline {
-                        return Err(e);
-                    } else {
-                        let time_left = deadline.saturating_duration_since(now);
-                        thread::sleep(std::cmp::min(time_left, Duration::from_millis(250)));
-                    }
+                    last_error = e;
+                    None
                 }
+            },
+        )
+        .map_err(|_| last_error)
+    }
+
+    /// Recreates an element from a token made by `remote_token`, possibly in another process.
+    ///
+    /// This uses private HIServices API, so it needs the `private-api` feature and may stop
+    /// working in a future macOS release.
+    #[cfg(feature = "private-api")]
+    pub fn from_remote_token(token: &[u8]) -> Result<Self, Error> {
+        let token = CFData::from_buffer(token);
+        let element = unsafe { _AXUIElementCreateWithRemoteToken(token.as_concrete_TypeRef()) };
+
+        if element.is_null() {
+            Err(Error::NotFound)
+        } else {
+            Ok(unsafe { Self::wrap_under_create_rule(element) })
+        }
+    }
+
+    /// Serializes this element into a token that `from_remote_token` can turn back into an
+    /// element, e.g. after sending it to another process.
+    ///
+    /// This uses private HIServices API, so it needs the `private-api` feature and may stop
+    /// working in a future macOS release.
+    #[cfg(feature = "private-api")]
+    pub fn remote_token(&self) -> Result<Vec<u8>, Error> {
+        let token = unsafe { _AXUIElementRemoteTokenCreate(self.0) };
+
+        if token.is_null() {
+            Err(Error::Ax(AXErrorKind::Failure))
+        } else {
+            Ok(unsafe { CFData::wrap_under_create_rule(token) }
+                .bytes()
+                .to_vec())
+        }
+    }
+
+    /// Whether the UI object behind this element still exists, e.g. its window hasn't closed.
+    pub fn is_valid(&self) -> bool {
+        !matches!(self.role(), Err(Error::Ax(AXErrorKind::InvalidUIElement)))
+    }
+
+    /// All of this element's attributes, with any elements they refer to summarized.
+    pub fn debug_shallow(&self) -> DebugElement<'_> {
+        self.debug_depth(0)
+    }
+
+    pub fn debug_depth(&self, depth: usize) -> DebugElement<'_> {
+        DebugElement {
+            element: self,
+            depth,
+            visited: None,
+        }
+    }
+
+    /// Like `debug_shallow`, but only reads `attributes` instead of everything the element
+    /// supports. Some apps are slow to answer, or do real work, for certain attributes.
+    pub fn debug_with<'a>(&'a self, attributes: &'a [AXAttribute<CFType>]) -> DebugAttributes<'a> {
+        DebugAttributes {
+            element: self,
+            attributes,
+        }
+    }
+
+    pub(crate) fn debug_nested<'a>(
+        &'a self,
+        depth: usize,
+        visited: &'a RefCell<HashSet<AXUIElement>>,
+    ) -> DebugElement<'a> {
+        DebugElement {
+            element: self,
+            depth,
+            visited: Some(visited),
+        }
+    }
+
+    pub fn descendants(&self) -> Descendants {
+        Descendants::new(self)
+    }
+
+    pub fn ancestors(&self) -> Ancestors {
+        Ancestors::new(self)
+    }
+
+    /// The element's frame, from AXFrame or else from its position and size. Fails if either of
+    /// those can't be read, rather than guessing the missing half.
+    pub fn window_frame(&self) -> Result<CGRect, Error> {
+        if let Ok(frame) = self.frame() {
+            return Ok(frame);
+        }
+
+        // Not every window exposes AXFrame; build it from position and size instead.
+        Ok(CGRect::new(&self.position()?, &self.size()?))
+    }
+
+    /// This element's frame with its origin relative to the top-left corner of the window that
+    /// contains it. Returns `Error::NotFound` if the element isn't in a window.
+    pub fn frame_in_window(&self) -> Result<CGRect, Error> {
+        let window = match self.window() {
+            Ok(window) => window,
+            // Not every element reports AXWindow, so look for it among the ancestors.
+            Err(_) => self
+                .ancestors()
+                .find(|ancestor| ancestor.role().is_ok_and(|role| role == kAXWindowRole))
+                .ok_or(Error::NotFound)?,
+        };
+        let frame = self.window_frame()?;
+        let window_frame = window.window_frame()?;
+
+        Ok(CGRect::new(
+            &CGPoint::new(
+                frame.origin.x - window_frame.origin.x,
+                frame.origin.y - window_frame.origin.y,
+            ),
+            &frame.size,
+        ))
+    }
+
+    pub fn frame_contains_point(&self, point: CGPoint) -> Result<bool, Error> {
+        Ok(geometry::rect_contains_point(self.window_frame()?, point))
+    }
+
+    pub fn frame_intersects(&self, rect: CGRect) -> Result<bool, Error> {
+        Ok(geometry::rects_intersect(self.window_frame()?, rect))
+    }
+
+    pub fn set_frame(&self, frame: CGRect) -> Result<(), Error> {
+        // Apps clamp the size so the window fits on screen at its current position; resizing
+        // again after the move gets the size we actually asked for.
+        self.set_size(frame.size)?;
+        self.set_position(frame.origin)?;
+        self.set_size(frame.size)
+    }
+
+    /// Raises this window and makes its application frontmost.
+    ///
+    /// If the window lives on another Space, macOS may switch to that Space or leave the window
+    /// where it is, depending on the user's Mission Control settings; nothing here can override
+    /// that.
+    pub fn activate(&self) -> Result<(), Error> {
+        self.raise()?;
+        Self::application(self.pid()?).set_frontmost(CFBoolean::true_value())
+    }
+
+    pub fn increment_by(&self, steps: usize) -> Result<(), Error> {
+        (0..steps).try_for_each(|_| self.increment())
+    }
+
+    pub fn decrement_by(&self, steps: usize) -> Result<(), Error> {
+        (0..steps).try_for_each(|_| self.decrement())
+    }
+
+    pub fn set_value_f64(&self, value: f64) -> Result<(), Error> {
+        self.set_value(CFNumber::from(value).as_CFType())
+    }
+
+    pub fn set_value_string(&self, value: &str) -> Result<(), Error> {
+        self.set_value(CFString::new(value).as_CFType())
+    }
+
+    pub fn set_value_bool(&self, value: bool) -> Result<(), Error> {
+        self.set_value(CFBoolean::from(value).as_CFType())
+    }
+
+    /// Focuses this element and types `text` into it by posting synthesized key events to its
+    /// application.
+    ///
+    /// This goes through the event system rather than the accessibility API, so it comes with
+    /// extra caveats:
+    ///
+    /// - Posting events needs its own permission. On recent macOS versions the process must be
+    ///   trusted for accessibility (see `permissions`), and sandboxed apps can't post events to
+    ///   other apps at all.
+    /// - Events are dropped while another app has secure input enabled, e.g. while a password
+    ///   field is focused.
+    /// - The events are delivered asynchronously, so the element's value may not reflect the
+    ///   text as soon as this returns.
+    ///
+    /// Returns `Error::EventPost` if the key events can't be created.
+    pub fn type_text(&self, text: &str) -> Result<(), Error> {
+        let pid = self.pid()?;
+        self.set_focused(true)?;
+
+        let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+            .map_err(|_| Error::EventPost)?;
+        let mut buf = [0; 2];
+
+        for ch in text.chars() {
+            let utf16 = ch.encode_utf16(&mut buf);
+
+            for keydown in [true, false] {
+                // The keycode is ignored once a Unicode string is attached.
+                let event = CGEvent::new_keyboard_event(source.clone(), 0, keydown)
+                    .map_err(|_| Error::EventPost)?;
+                event.set_string_from_utf16_unchecked(utf16);
+                event.post_to_pid(pid);
             }
         }
+
+        Ok(())
     }
 
     pub fn attribute_names(&self) -> Result<CFArray<CFString>, Error> {
@@ -111,9 +474,22 @@ impl AXUIElement {
         }
     }
 
+    /// Like `attribute_names`, but hands out the names one at a time instead of as an array.
+    pub fn attribute_names_iter(&self) -> Result<impl Iterator<Item = CFString>, Error> {
+        let names = self.attribute_names()?;
+        Ok((0..names.len()).filter_map(move |i| names.get(i).map(|name| name.clone())))
+    }
+
+    pub fn attributes(&self) -> Result<Vec<AXAttribute<CFType>>, Error> {
+        let names = self.attribute_names()?;
+        Ok(names.iter().map(|name| AXAttribute::new(&name)).collect())
+    }
+
+    /// Reads `attribute`, checking that the value really is a `T`. A value of another CF type
+    /// comes back as `Error::UnexpectedType`.
     pub fn attribute<T: TCFType>(&self, attribute: &AXAttribute<T>) -> Result<T, Error> {
-        let res = unsafe {
-            Ok(T::wrap_under_create_rule(T::Ref::from_void_ptr(
+        let value = unsafe {
+            CFType::wrap_under_create_rule(
                 ax_call(|x| {
                     AXUIElementCopyAttributeValue(
                         self.0,
@@ -122,17 +498,280 @@ impl AXUIElement {
                     )
                 })
                 .map_err(Error::Ax)?,
-            )))
+            )
         };
-        if let Ok(val) = &res {
-            if T::type_id() != CFType::type_id() && !val.instance_of::<T>() {
-                return Err(Error::UnexpectedType {
-                    expected: T::type_id(),
-                    received: val.type_of(),
-                });
+        cast_attribute(value)
+    }
+
+    /// Reads `attribute` untyped and then downcasts it to `T`, returning
+    /// `Error::UnexpectedType` if the app reported a value of another CF type.
+    pub fn get<T: TCFType>(&self, attribute: &AXAttribute<T>) -> Result<T, Error> {
+        cast_attribute(self.attribute(&AXAttribute::new(attribute.as_CFString()))?)
+    }
+
+    /// Polls `attribute` until its value satisfies `predicate`, and returns that value. Read
+    /// errors are retried too, since the attribute may not exist yet, except when the element
+    /// itself has gone away. Gives up with `Error::TimedOut` after `timeout`.
+    pub fn wait_for_attribute<T: TCFType>(
+        &self,
+        attribute: &AXAttribute<T>,
+        predicate: impl Fn(&T) -> bool,
+        timeout: Duration,
+    ) -> Result<T, Error> {
+        poll_until(timeout, Duration::from_millis(100), || {
+            match self.attribute(attribute) {
+                Ok(value) if predicate(&value) => Some(Ok(value)),
+                Err(e @ Error::Ax(AXErrorKind::InvalidUIElement)) => Some(Err(e)),
+                _ => None,
             }
+        })
+    }
+
+    /// Reads an attribute on a blocking thread so an async caller isn't stalled by a slow app.
+    ///
+    /// The read still gives up after the element's messaging timeout; see
+    /// `set_messaging_timeout`.
+    #[cfg(feature = "tokio")]
+    pub fn attribute_async<T: TCFType>(
+        &self,
+        attribute: &AXAttribute<T>,
+    ) -> impl std::future::Future<Output = Result<T, Error>> + Send {
+        let element = SendableElement::new(self.clone());
+        let name = attribute.as_CFString().to_string();
+
+        // Spawn inside the future, so that creating it doesn't need a runtime.
+        async move {
+            let read = tokio::task::spawn_blocking(move || {
+                let element = element.into_inner();
+                element
+                    .attribute(&AXAttribute::new(&CFString::new(&name)))
+                    .map(SendableValue)
+            });
+            let value = match read.await {
+                Ok(result) => result?,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(_) => return Err(Error::Cancelled),
+            };
+            cast_attribute(value.0)
+        }
+    }
+
+    /// Reads every attribute the element reports, leaving out the ones that fail to read.
+    pub fn attributes_map(&self) -> Result<HashMap<String, CFType>, Error> {
+        let names = self.attribute_names()?;
+
+        Ok(names
+            .iter()
+            .filter_map(|name| {
+                let value = self.attribute(&AXAttribute::new(&name)).ok()?;
+                Some((name.to_string(), value))
+            })
+            .collect())
+    }
+
+    /// The element's value as text, for strings, numbers and booleans.
+    pub fn value_as_string(&self) -> Result<String, Error> {
+        let value = self.value()?;
+
+        if let Some(string) = value.downcast::<CFString>() {
+            Ok(string.to_string())
+        } else if let Some(number) = value.downcast::<CFNumber>() {
+            // Integral values print without a trailing `.0`.
+            number
+                .to_i64()
+                .map(|n| n.to_string())
+                .or_else(|| number.to_f64().map(|n| n.to_string()))
+                .ok_or(Error::UnconvertibleNumber)
+        } else if let Some(boolean) = value.downcast::<CFBoolean>() {
+            Ok(bool::from(boolean).to_string())
+        } else {
+            Err(Error::UnexpectedType {
+                expected: CFString::type_id(),
+                received: value.type_of(),
+            })
+        }
+    }
+
+    /// Reads an array-valued attribute into an owned `Vec`.
+    pub fn attribute_vec<T: TCFType + Clone>(
+        &self,
+        attribute: &AXAttribute<CFArray<T>>,
+    ) -> Result<Vec<T>, Error> {
+        let array = self.attribute(attribute)?;
+        Ok(array.iter().map(|item| (*item).clone()).collect())
+    }
+
+    pub fn children_vec(&self) -> Result<Vec<AXUIElement>, Error> {
+        self.attribute_vec(&AXAttribute::children())
+    }
+
+    /// A short human-readable label for this element, roughly what a screen reader would say.
+    ///
+    /// Uses the first non-empty one of the title, description, value, placeholder, help and
+    /// role description, in that order, and falls back to the bare role. Returns an empty
+    /// string if none of these can be read.
+    pub fn accessible_label(&self) -> String {
+        let candidates: [&dyn Fn() -> Result<String, Error>; 6] = [
+            &|| self.title().map(|s| s.to_string()),
+            &|| self.description().map(|s| s.to_string()),
+            &|| self.value_as_string(),
+            &|| self.placeholder_value().map(|s| s.to_string()),
+            &|| self.help().map(|s| s.to_string()),
+            &|| self.role_description().map(|s| s.to_string()),
+        ];
+
+        candidates
+            .iter()
+            .filter_map(|read| read().ok())
+            .find(|label| !label.is_empty())
+            .or_else(|| self.role().ok().map(|s| s.to_string()))
+            .unwrap_or_default()
+    }
+
+    pub fn attribute_value_count<T>(&self, attribute: &AXAttribute<T>) -> Result<isize, Error> {
+        unsafe {
+            ax_call(|x| {
+                AXUIElementGetAttributeValueCount(
+                    self.0,
+                    attribute.as_CFString().as_concrete_TypeRef(),
+                    x,
+                )
+            })
+            .map_err(Error::Ax)
+        }
+    }
+
+    /// Fetches up to `max` items of an array attribute, starting at `index`.
+    pub fn attribute_values_range<T: TCFType>(
+        &self,
+        attribute: &AXAttribute<CFArray<T>>,
+        index: usize,
+        max: usize,
+    ) -> Result<CFArray<T>, Error> {
+        unsafe {
+            Ok(CFArray::wrap_under_create_rule(
+                ax_call(|x| {
+                    AXUIElementCopyAttributeValues(
+                        self.0,
+                        attribute.as_CFString().as_concrete_TypeRef(),
+                        index as CFIndex,
+                        max as CFIndex,
+                        x,
+                    )
+                })
+                .map_err(Error::Ax)?,
+            ))
         }
-        res
+    }
+
+    pub fn attribute_values(
+        &self,
+        attributes: &[AXAttribute<CFType>],
+        stop_on_error: bool,
+    ) -> Result<Vec<Result<CFType, Error>>, Error> {
+        let names: Vec<CFString> = attributes
+            .iter()
+            .map(|attr| attr.as_CFString().clone())
+            .collect();
+        let names = CFArray::from_CFTypes(&names);
+        let options = if stop_on_error {
+            kAXCopyMultipleAttributeOptionStopOnError
+        } else {
+            0
+        };
+        let values: CFArray<CFType> = unsafe {
+            CFArray::wrap_under_create_rule(
+                ax_call(|x| {
+                    AXUIElementCopyMultipleAttributeValues(
+                        self.0,
+                        names.as_concrete_TypeRef(),
+                        options,
+                        x,
+                    )
+                })
+                .map_err(Error::Ax)?,
+            )
+        };
+
+        // Attributes that couldn't be read come back as an AXValue wrapping the AXError.
+        Ok(values
+            .iter()
+            .map(|value| {
+                if let Ok(AXErrorValue(code)) = AXErrorValue::from_cf(&value) {
+                    return Err(Error::Ax(code.into()));
+                }
+                Ok(CFType::clone(&value))
+            })
+            .collect())
+    }
+
+    pub fn parameterized_attribute_names(&self) -> Result<CFArray<CFString>, Error> {
+        unsafe {
+            Ok(CFArray::wrap_under_create_rule(
+                ax_call(|x| AXUIElementCopyParameterizedAttributeNames(self.0, x))
+                    .map_err(Error::Ax)?,
+            ))
+        }
+    }
+
+    pub fn parameterized_attribute<P: TCFType, T: TCFType>(
+        &self,
+        attribute: &AXParameterizedAttribute<P, T>,
+        parameter: impl Into<P>,
+    ) -> Result<T, Error> {
+        let parameter = parameter.into();
+        let value = unsafe {
+            CFType::wrap_under_create_rule(
+                ax_call(|x| {
+                    AXUIElementCopyParameterizedAttributeValue(
+                        self.0,
+                        attribute.as_CFString().as_concrete_TypeRef(),
+                        parameter.as_CFTypeRef(),
+                        x,
+                    )
+                })
+                .map_err(Error::Ax)?,
+            )
+        };
+        cast_attribute(value)
+    }
+
+    pub fn bounds_for_range(&self, range: CFRange) -> Result<CGRect, Error> {
+        let bounds = self.parameterized_attribute(
+            &AXParameterizedAttribute::bounds_for_range(),
+            AXValue::new(&range)?,
+        )?;
+        Ok(bounds.value()?)
+    }
+
+    /// Scrolls a text element so `range` is on screen. Text views that don't support it return
+    /// `AttributeUnsupported` or `IllegalArgument`.
+    pub fn scroll_to_visible_range(&self, range: CFRange) -> Result<(), Error> {
+        self.set_visible_character_range(range)
+    }
+
+    pub fn string_for_range(&self, range: CFRange) -> Result<CFString, Error> {
+        self.parameterized_attribute(
+            &AXParameterizedAttribute::string_for_range(),
+            AXValue::new(&range)?,
+        )
+    }
+
+    /// The line number of the character at `index` in a text element.
+    pub fn line_for_index(&self, index: i64) -> Result<i64, Error> {
+        let line = self.parameterized_attribute(
+            &AXParameterizedAttribute::line_for_index(),
+            CFNumber::from(index),
+        )?;
+        <i64 as NumberKind>::from_number(&line).map_err(|_| Error::UnconvertibleNumber)
+    }
+
+    pub fn range_for_line(&self, line: i64) -> Result<CFRange, Error> {
+        let range = self.parameterized_attribute(
+            &AXParameterizedAttribute::range_for_line(),
+            CFNumber::from(line),
+        )?;
+        Ok(range.value()?)
     }
 
     pub fn set_attribute<T: TCFType>(
@@ -168,6 +807,19 @@ impl AXUIElement {
         Ok(settable != 0)
     }
 
+    pub fn element_at_position(&self, x: f32, y: f32) -> Result<AXUIElement, Error> {
+        unsafe {
+            let element = ax_call(|e| AXUIElementCopyElementAtPosition(self.0, x, y, e))
+                .map_err(Error::Ax)?;
+
+            if element.is_null() {
+                Err(Error::NotFound)
+            } else {
+                Ok(Self::wrap_under_create_rule(element))
+            }
+        }
+    }
+
     pub fn action_names(&self) -> Result<CFArray<CFString>, Error> {
         unsafe {
             Ok(CFArray::wrap_under_create_rule(
@@ -176,6 +828,17 @@ impl AXUIElement {
         }
     }
 
+    pub fn action_description(&self, name: &CFString) -> Result<CFString, Error> {
+        unsafe {
+            Ok(CFString::wrap_under_create_rule(
+                ax_call(|x| {
+                    AXUIElementCopyActionDescription(self.0, name.as_concrete_TypeRef(), x)
+                })
+                .map_err(Error::Ax)?,
+            ))
+        }
+    }
+
     pub fn perform_action(&self, name: &CFString) -> Result<(), Error> {
         unsafe {
             Ok(
@@ -185,6 +848,10 @@ impl AXUIElement {
         }
     }
 
+    pub fn pid(&self) -> Result<pid_t, Error> {
+        unsafe { ax_call(|x| AXUIElementGetPid(self.0, x)).map_err(Error::Ax) }
+    }
+
     pub fn set_messaging_timeout(&self, timeout: f32) -> Result<(), Error> {
         unsafe {
             Ok(
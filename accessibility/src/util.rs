@@ -1,7 +1,19 @@
 use accessibility_sys::{kAXErrorSuccess, AXError};
-use std::mem::MaybeUninit;
+use cocoa::{
+    base::{id, nil},
+    foundation::NSString,
+};
+use core_foundation::base::{CFType, TCFType, TCFTypeRef};
+use std::{
+    ffi::CStr,
+    mem::MaybeUninit,
+    thread,
+    time::{Duration, Instant},
+};
 
-pub(crate) unsafe fn ax_call<F, V>(f: F) -> Result<V, AXError>
+use crate::{AXErrorKind, Error};
+
+pub(crate) unsafe fn ax_call<F, V>(f: F) -> Result<V, AXErrorKind>
 where
     F: Fn(*mut V) -> AXError,
 {
@@ -9,21 +21,101 @@ where
     let err = (f)(result.as_mut_ptr());
 
     if err != kAXErrorSuccess {
-        return Err(err);
+        return Err(err.into());
     }
 
     Ok(result.assume_init())
 }
 
-pub(crate) unsafe fn ax_call_void<F>(f: F) -> Result<(), AXError>
+pub(crate) unsafe fn ax_call_void<F>(f: F) -> Result<(), AXErrorKind>
 where
     F: Fn() -> AXError,
 {
     let err = (f)();
 
     if err != kAXErrorSuccess {
-        return Err(err);
+        return Err(err.into());
     }
 
     Ok(())
 }
+
+pub(crate) unsafe fn ns_string_to_string(string: id) -> String {
+    if string == nil {
+        return String::new();
+    }
+
+    CStr::from_ptr(string.UTF8String())
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub(crate) fn cast_attribute<T: TCFType>(value: CFType) -> Result<T, Error> {
+    if T::type_id() != CFType::type_id() && !value.instance_of::<T>() {
+        return Err(Error::UnexpectedType {
+            expected: T::type_id(),
+            received: value.type_of(),
+        });
+    }
+    Ok(unsafe { T::wrap_under_get_rule(T::Ref::from_void_ptr(value.as_CFTypeRef())) })
+}
+
+/// Calls `f` every `interval` until it returns `Some`, giving up with `Error::TimedOut` once
+/// `timeout` has passed. `f` always runs at least once, and once more at the deadline.
+pub(crate) fn poll_until<T, F>(timeout: Duration, interval: Duration, mut f: F) -> Result<T, Error>
+where
+    F: FnMut() -> Option<Result<T, Error>>,
+{
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(result) = f() {
+            return result;
+        }
+
+        let now = Instant::now();
+
+        if now >= deadline {
+            return Err(Error::TimedOut);
+        } else {
+            let time_left = deadline.saturating_duration_since(now);
+            thread::sleep(std::cmp::min(time_left, interval));
+        }
+    }
+}
+
+/// An element for tests to use as a stand-in. Application elements can be created for any pid
+/// without a running app or accessibility permissions; they compare equal by pid and report it
+/// back.
+#[cfg(test)]
+pub(crate) fn test_element(pid: accessibility_sys::pid_t) -> crate::AXUIElement {
+    crate::AXUIElement::application(pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_until_returns_the_first_result() {
+        let mut calls = 0;
+        let result = poll_until(Duration::from_secs(1), Duration::ZERO, || {
+            calls += 1;
+            (calls == 3).then_some(Ok(calls))
+        });
+
+        assert!(matches!(result, Ok(3)));
+    }
+
+    #[test]
+    fn poll_until_times_out_after_calling_at_least_once() {
+        let mut calls = 0;
+        let result: Result<(), Error> = poll_until(Duration::ZERO, Duration::ZERO, || {
+            calls += 1;
+            None
+        });
+
+        assert!(matches!(result, Err(Error::TimedOut)));
+        assert_eq!(calls, 1);
+    }
+}
@@ -1,10 +1,15 @@
 use std::{ffi::c_void, marker::PhantomData};
 
 use accessibility_sys::{
-    kAXErrorFailure, kAXErrorSuccess, kAXValueTypeCFRange, kAXValueTypeCGPoint, kAXValueTypeCGRect,
-    kAXValueTypeCGSize, AXValueCreate, AXValueGetTypeID, AXValueGetValue, AXValueRef, AXValueType,
+    kAXErrorFailure, kAXErrorSuccess, kAXValueTypeAXError, kAXValueTypeCFRange,
+    kAXValueTypeCGPoint, kAXValueTypeCGRect, kAXValueTypeCGSize, AXError, AXValueCreate,
+    AXValueGetType, AXValueGetTypeID, AXValueGetValue, AXValueRef, AXValueType,
+};
+use core_foundation::{
+    base::{CFRange, CFType, CFTypeID, TCFType},
+    declare_TCFType, impl_CFTypeDescription, impl_TCFType,
+    number::CFNumber,
 };
-use core_foundation::{base::CFRange, declare_TCFType, impl_CFTypeDescription, impl_TCFType};
 use core_graphics_types::geometry::{CGPoint, CGRect, CGSize};
 
 use crate::util::ax_call;
@@ -25,24 +30,51 @@ impl AXValueKind for CGRect {
 impl AXValueKind for CFRange {
     const TYPE: AXValueType = kAXValueTypeCFRange;
 }
+impl AXValueKind for AXErrorValue {
+    const TYPE: AXValueType = kAXValueTypeAXError;
+}
+
+/// The payload of an AXValue holding an `AXError`. `AXError` is a plain `i32`, so it gets its
+/// own type here to keep it apart from ordinary integers.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AXErrorValue(pub AXError);
 
 declare_TCFType!(AXValue<T: AXValueKind>, AXValueRef);
 impl_TCFType!(AXValue<T: AXValueKind>, AXValueRef, AXValueGetTypeID);
 impl_CFTypeDescription!(AXValue<T: AXValueKind>);
 
+/// A value didn't hold the kind of data asked for. `expected` and `received` are equal when the
+/// CF type matched but the payload didn't, e.g. an AXValue with a different tag.
 #[derive(Copy, Clone, Debug)]
-pub struct WrongType;
+pub struct WrongType {
+    pub expected: CFTypeID,
+    pub received: CFTypeID,
+}
+
+impl WrongType {
+    fn payload(type_id: CFTypeID) -> Self {
+        WrongType {
+            expected: type_id,
+            received: type_id,
+        }
+    }
+}
 
 impl<T: AXValueKind> AXValue<T> {
     pub fn new(val: &T) -> Result<Self, WrongType> {
         let ptr = unsafe { AXValueCreate(T::TYPE, val as *const T as *const c_void) };
         if ptr.is_null() {
-            Err(WrongType)
+            Err(WrongType::payload(Self::type_id()))
         } else {
             Ok(Self(ptr, PhantomData))
         }
     }
 
+    pub fn value_type(&self) -> AXValueType {
+        unsafe { AXValueGetType(self.0) }
+    }
+
     pub fn value(&self) -> Result<T, WrongType> {
         unsafe {
             ax_call(
@@ -51,7 +83,134 @@ impl<T: AXValueKind> AXValue<T> {
                     false => kAXErrorFailure,
                 },
             )
-            .map_err(|_| WrongType)
+            .map_err(|_| WrongType::payload(Self::type_id()))
+        }
+    }
+}
+
+/// The payload of an AXValue whose type isn't known up front.
+#[derive(Copy, Clone, Debug)]
+pub enum AXValueData {
+    Point(CGPoint),
+    Size(CGSize),
+    Rect(CGRect),
+    Range(CFRange),
+    Error(AXErrorValue),
+    /// A type this crate doesn't know how to decode, or one whose payload couldn't be read.
+    Unknown(AXValueType),
+}
+
+impl AXValueData {
+    /// Returns `None` if `value` isn't an AXValue at all.
+    #[allow(non_upper_case_globals)]
+    pub fn from_cf(value: &CFType) -> Option<Self> {
+        if value.type_of() != unsafe { AXValueGetTypeID() } {
+            return None;
+        }
+
+        let value = value.as_CFTypeRef() as AXValueRef;
+        let value_type = unsafe { AXValueGetType(value) };
+        fn get<T: AXValueKind>(value: AXValueRef) -> Option<T> {
+            unsafe { AXValue::<T>::wrap_under_get_rule(value) }
+                .value()
+                .ok()
+        }
+
+        Some(
+            match value_type {
+                kAXValueTypeCGPoint => get(value).map(Self::Point),
+                kAXValueTypeCGSize => get(value).map(Self::Size),
+                kAXValueTypeCGRect => get(value).map(Self::Rect),
+                kAXValueTypeCFRange => get(value).map(Self::Range),
+                kAXValueTypeAXError => get(value).map(Self::Error),
+                _ => None,
+            }
+            .unwrap_or(Self::Unknown(value_type)),
+        )
+    }
+}
+
+pub trait NumberKind: Sized {
+    fn from_number(number: &CFNumber) -> Result<Self, WrongType>;
+}
+
+macro_rules! number_kind {
+    ($typ:ty, $getter:ident) => {
+        impl NumberKind for $typ {
+            // CFNumberGetValue refuses lossy conversions, e.g. a fractional float to an integer.
+            fn from_number(number: &CFNumber) -> Result<Self, WrongType> {
+                number
+                    .$getter()
+                    .ok_or(WrongType::payload(CFNumber::type_id()))
+            }
         }
+    };
+}
+
+number_kind!(i32, to_i32);
+number_kind!(i64, to_i64);
+number_kind!(f32, to_f32);
+number_kind!(f64, to_f64);
+
+/// Types that can be read out of an attribute value whose type isn't known up front.
+///
+/// The CF type is checked first, and for AXValues the payload tag as well, so asking for the wrong
+/// kind of data returns `WrongType` instead of reinterpreting the payload.
+pub trait FromCFType: Sized {
+    fn from_cf(value: &CFType) -> Result<Self, WrongType>;
+}
+
+fn from_ax_value<T: AXValueKind>(value: &CFType) -> Result<T, WrongType> {
+    let type_id = AXValue::<T>::type_id();
+    if value.type_of() != type_id {
+        return Err(WrongType {
+            expected: type_id,
+            received: value.type_of(),
+        });
+    }
+    let value = unsafe { AXValue::<T>::wrap_under_get_rule(value.as_CFTypeRef() as AXValueRef) };
+    if value.value_type() != T::TYPE {
+        return Err(WrongType::payload(type_id));
     }
+    value.value()
+}
+
+macro_rules! ax_value_from_cf {
+    ($typ:ty) => {
+        impl FromCFType for $typ {
+            fn from_cf(value: &CFType) -> Result<Self, WrongType> {
+                from_ax_value(value)
+            }
+        }
+    };
+}
+
+ax_value_from_cf!(CGPoint);
+ax_value_from_cf!(CGSize);
+ax_value_from_cf!(CGRect);
+ax_value_from_cf!(CFRange);
+ax_value_from_cf!(AXErrorValue);
+
+macro_rules! number_from_cf {
+    ($typ:ty) => {
+        impl FromCFType for $typ {
+            fn from_cf(value: &CFType) -> Result<Self, WrongType> {
+                let number = value.downcast::<CFNumber>().ok_or(WrongType {
+                    expected: CFNumber::type_id(),
+                    received: value.type_of(),
+                })?;
+                <$typ as NumberKind>::from_number(&number)
+            }
+        }
+    };
+}
+
+number_from_cf!(i32);
+number_from_cf!(i64);
+number_from_cf!(f32);
+number_from_cf!(f64);
+
+/// Reads `value` as a `T`, e.g. `decode::<i64>(&value)` or `decode::<CGRect>(&value)`.
+pub fn decode<T: FromCFType>(value: &CFType) -> Result<T, WrongType> {
+    T::from_cf(value)
 }
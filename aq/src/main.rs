@@ -1,51 +1,95 @@
 use accessibility::{
-    AXAttribute, AXUIElement, AXUIElementAttributes, TreeVisitor, TreeWalker, TreeWalkerFlow,
+    snapshot::{Snapshot, SnapshotValue},
+    AXAttribute, AXErrorKind, AXUIElement, AXUIElementAttributes, Error, StatsVisitor, TreeVisitor,
+    TreeWalker, TreeWalkerFlow,
 };
 use core_foundation::{array::CFArray, string::CFString};
-use std::cell::Cell;
+use std::{cell::RefCell, str::FromStr};
 use structopt::StructOpt;
 
 struct PrintyBoi {
-    level: Cell<usize>,
-    max_depth: usize,
     indent: String,
     children: AXAttribute<CFArray<AXUIElement>>,
+    visible_children: Option<AXAttribute<CFArray<AXUIElement>>>,
+    attrs: Option<Vec<String>>,
 }
 
 impl PrintyBoi {
-    pub fn new_with_indentation(indent: usize, max_depth: usize) -> Self {
+    pub fn new_with_indentation(indent: usize) -> Self {
         Self {
-            level: Cell::new(0),
-            max_depth,
             indent: " ".repeat(indent),
             children: AXAttribute::children(),
+            visible_children: None,
+            attrs: None,
         }
     }
+
+    /// Counts visible children where an element has them, like the walker with
+    /// `--visible-only`.
+    pub fn with_visible_children(mut self, visible_children: bool) -> Self {
+        self.visible_children = visible_children.then(AXAttribute::visible_children);
+        self
+    }
+
+    /// Restricts printed attributes to `attrs`; `None` prints everything.
+    pub fn with_attrs(mut self, attrs: Option<Vec<String>>) -> Self {
+        self.attrs = normalize_attrs(attrs);
+        self
+    }
+
+    fn child_count(&self, element: &AXUIElement) -> isize {
+        if let Some(visible_children) = &self.visible_children {
+            match element.attribute_value_count(visible_children) {
+                Err(Error::Ax(AXErrorKind::AttributeUnsupported | AXErrorKind::NoValue)) => {}
+                result => return result.unwrap_or(0),
+            }
+        }
+        element.attribute_value_count(&self.children).unwrap_or(0)
+    }
+
+    fn wants_attr(&self, name: &str) -> bool {
+        wants_attr(&self.attrs, name)
+    }
+}
+
+fn normalize_attrs(attrs: Option<Vec<String>>) -> Option<Vec<String>> {
+    attrs.map(|attrs| attrs.iter().map(|a| normalize_attr(a)).collect())
+}
+
+fn wants_attr(attrs: &Option<Vec<String>>, name: &str) -> bool {
+    match attrs {
+        Some(attrs) => attrs.contains(&normalize_attr(name)),
+        None => true,
+    }
+}
+
+// Lets `role`, `Role` and `AXRole` (or `role_description` and `AXRoleDescription`) match.
+fn normalize_attr(name: &str) -> String {
+    let name = name.strip_prefix("AX").unwrap_or(name);
+    name.replace('_', "").to_lowercase()
 }
 
 impl TreeVisitor for PrintyBoi {
-    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
-        let indent = self.indent.repeat(self.level.get());
+    fn enter_element(&self, element: &AXUIElement, depth: usize) -> TreeWalkerFlow {
+        let indent = self.indent.repeat(depth);
         let role = element.role().unwrap_or_else(|_| CFString::new(""));
 
-        if self.level.replace(self.level.get() + 1) >= self.max_depth {
-            return TreeWalkerFlow::SkipSubtree;
-        }
         println!(
             "{}- {} ({} children)",
             indent,
             role,
-            element.children().map(|a| a.len()).unwrap_or(0)
+            self.child_count(element)
         );
 
-        if let Ok(names) = element.attribute_names() {
-            for name in names.into_iter() {
-                if &*name == self.children.as_CFString() {
+        if let Ok(attributes) = element.attributes() {
+            for attribute in attributes {
+                let name = attribute.as_CFString();
+                if name == self.children.as_CFString() || !self.wants_attr(&name.to_string()) {
                     continue;
                 }
 
-                if let Ok(value) = element.attribute(&AXAttribute::new(&*name)) {
-                    println!["{}|. {}: {:?}", indent, *name, value];
+                if let Ok(value) = element.attribute(&attribute) {
+                    println!["{}|. {}: {:?}", indent, name, value];
                 }
             }
         }
@@ -53,24 +97,167 @@ impl TreeVisitor for PrintyBoi {
         TreeWalkerFlow::Continue
     }
 
-    fn exit_element(&self, _element: &AXUIElement) {
-        self.level.replace(self.level.get() - 1);
+    fn exit_element(&self, _element: &AXUIElement, _depth: usize) {}
+}
+
+/// Builds a `Snapshot` of whatever the walker visits, so the JSON output follows the same
+/// `--visible-only` and attribute filters as the text output.
+struct SnapshotBuilder {
+    children: String,
+    attrs: Option<Vec<String>>,
+    // The elements entered but not yet exited, innermost last.
+    stack: RefCell<Vec<Snapshot>>,
+    root: RefCell<Option<Snapshot>>,
+}
+
+impl SnapshotBuilder {
+    fn new(attrs: Option<Vec<String>>) -> Self {
+        Self {
+            children: AXAttribute::children().as_CFString().to_string(),
+            attrs: normalize_attrs(attrs),
+            stack: RefCell::new(Vec::new()),
+            root: RefCell::new(None),
+        }
+    }
+
+    fn into_snapshot(self) -> Option<Snapshot> {
+        self.root.into_inner()
+    }
+}
+
+impl TreeVisitor for SnapshotBuilder {
+    fn enter_element(&self, element: &AXUIElement, _depth: usize) -> TreeWalkerFlow {
+        let attributes = element
+            .attributes_map()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(name, _)| *name != self.children && wants_attr(&self.attrs, name))
+            .map(|(name, value)| (name, SnapshotValue::from_cf(&value)))
+            .collect();
+
+        self.stack.borrow_mut().push(Snapshot {
+            attributes,
+            children: Vec::new(),
+        });
+        TreeWalkerFlow::Continue
+    }
+
+    fn exit_element(&self, _element: &AXUIElement, _depth: usize) {
+        let mut stack = self.stack.borrow_mut();
+        let snapshot = stack.pop().expect("exit without matching enter");
+
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(snapshot),
+            None => *self.root.borrow_mut() = Some(snapshot),
+        }
+    }
+}
+
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{}`, expected `text` or `json`", s)),
+        }
     }
 }
 
 #[derive(StructOpt)]
 pub struct Opt {
-    //pub pid: i32,
+    #[structopt(long)]
+    pub pid: Option<i32>,
     pub max_depth: usize,
+    /// Only print these attributes, e.g. `role,title,value`.
+    #[structopt(long, use_delimiter = true, conflicts_with = "no-attrs")]
+    pub attrs: Option<Vec<String>>,
+    /// Only print roles and child counts.
+    #[structopt(long)]
+    pub no_attrs: bool,
+    /// Only descend into the visible rows of lists and tables.
+    #[structopt(long)]
+    pub visible_only: bool,
+    /// Print element counts per role and the tree's depth instead of the elements.
+    #[structopt(long, conflicts_with_all = &["attrs", "no-attrs"])]
+    pub stats: bool,
+    #[structopt(long, default_value = "text")]
+    pub format: Format,
 }
 
 fn main() -> Result<(), i32> {
     let opt = Opt::from_args();
-    //let app = AXUIElement::application(opt.pid);
-    let app = AXUIElement::system_wide();
-    let printy = PrintyBoi::new_with_indentation(4, opt.max_depth);
-    let walker = TreeWalker::new();
+    let app = match opt.pid {
+        Some(pid) => AXUIElement::application_with_pid(pid).map_err(|e| {
+            eprintln!("no application with pid {}: {}", pid, e);
+            1
+        })?,
+        None => AXUIElement::system_wide(),
+    };
+    // max_depth counts printed levels, including the root.
+    let walker = TreeWalker::builder()
+        .max_depth(opt.max_depth.saturating_sub(1))
+        .visible_children(opt.visible_only)
+        .build();
+
+    let attrs = if opt.no_attrs {
+        Some(Vec::new())
+    } else {
+        opt.attrs
+    };
+
+    if opt.stats {
+        let stats = StatsVisitor::new();
+        walker.walk(&app, &stats);
+
+        let stats = stats.stats();
+        if let Format::Json = opt.format {
+            let stats = serde_json::json!({
+                "roles": stats.roles,
+                "total": stats.total,
+                "max_depth": stats.max_depth,
+            });
+            println!("{}", serde_json::to_string_pretty(&stats).map_err(|_| 1)?);
+            return Ok(());
+        }
+
+        for (role, count) in &stats.roles {
+            println!(
+                "{:>8} {}",
+                count,
+                if role.is_empty() { "(no role)" } else { role }
+            );
+        }
+        println!("{:>8} total", stats.total);
+        println!("max depth: {}", stats.max_depth);
+
+        return Ok(());
+    }
+
+    match opt.format {
+        Format::Text => {
+            let printy = PrintyBoi::new_with_indentation(4)
+                .with_attrs(attrs)
+                .with_visible_children(opt.visible_only);
+            walker.walk(&app, &printy);
+        }
+        Format::Json => {
+            let builder = SnapshotBuilder::new(attrs);
+            walker.walk(&app, &builder);
+            let snapshot = builder.into_snapshot();
+
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&snapshot).map_err(|_| 1)?
+            );
+        }
+    }
 
-    walker.walk(&app, &printy);
     Ok(())
 }